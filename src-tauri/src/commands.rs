@@ -1,5 +1,8 @@
+use crate::persistence::ExportFormat;
 use crate::state::AppState;
-use crate::task::TaskDto;
+use crate::task::{Priority, TaskDto};
+use chrono::NaiveDate;
+use std::collections::HashMap;
 use tauri::State;
 
 /// Retrieves all tasks as DTOs for the frontend.
@@ -8,10 +11,7 @@ pub fn get_tasks(state: State<AppState>) -> Result<Vec<TaskDto>, String> {
     let tasks = state.tasks.lock()
         .map_err(|_| "Failed to acquire lock".to_string())?;
 
-    let mut task_list: Vec<TaskDto> = tasks
-        .values()
-        .map(TaskDto::from)
-        .collect();
+    let mut task_list = TaskDto::build_list(&tasks);
 
     // Sort by creation date
     task_list.sort_by(|a, b| a.created_at.cmp(&b.created_at));
@@ -19,6 +19,115 @@ pub fn get_tasks(state: State<AppState>) -> Result<Vec<TaskDto>, String> {
     Ok(task_list)
 }
 
+/// Creates a new subtask nested under an existing parent task.
+#[tauri::command]
+pub fn add_subtask(state: State<AppState>, parent: String, name: String) -> Result<(), String> {
+    let trimmed_name = name.trim().to_string();
+
+    if trimmed_name.is_empty() {
+        return Err("Task name cannot be empty".to_string());
+    }
+
+    state.add_subtask(&parent, trimmed_name)
+}
+
+/// Sets or clears the parent of a task.
+#[tauri::command]
+pub fn set_parent(state: State<AppState>, name: String, parent: Option<String>) -> Result<(), String> {
+    state.set_parent(&name, parent)
+}
+
+/// Merges tasks from another save file into the live state, for syncing across devices.
+#[tauri::command]
+pub fn import_tasks(state: State<AppState>, path: String) -> Result<(), String> {
+    state.merge_from(&path)
+}
+
+/// Adds a dependency edge so `task` depends on `depends_on`.
+#[tauri::command]
+pub fn add_dependency(state: State<AppState>, task: String, depends_on: String) -> Result<(), String> {
+    state.add_dependency(&task, &depends_on)
+}
+
+/// Removes a dependency edge from `task` to `depends_on`.
+#[tauri::command]
+pub fn remove_dependency(state: State<AppState>, task: String, depends_on: String) -> Result<(), String> {
+    state.remove_dependency(&task, &depends_on)
+}
+
+/// Returns the task's own time plus the rolled-up time of all transitive dependencies, in seconds.
+#[tauri::command]
+pub fn get_aggregated_time(state: State<AppState>, name: String) -> Result<u64, String> {
+    state.aggregated_time(&name).map(|d| d.as_secs())
+}
+
+/// Returns all tasks in dependency order.
+#[tauri::command]
+pub fn get_topological_order(state: State<AppState>) -> Result<Vec<String>, String> {
+    state.topological_order()
+}
+
+/// Returns, per task, the total seconds logged on each day in `[from, to]` (inclusive),
+/// keyed by ISO date string.
+#[tauri::command]
+pub fn get_report_by_day(
+    state: State<AppState>,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<HashMap<String, HashMap<String, u64>>, String> {
+    let report = state.report_by_day(from, to)?;
+    Ok(report
+        .into_iter()
+        .map(|(name, days)| {
+            let days = days
+                .into_iter()
+                .map(|(day, duration)| (day.format("%Y-%m-%d").to_string(), duration.as_secs()))
+                .collect();
+            (name, days)
+        })
+        .collect())
+}
+
+/// Returns, per task, the total seconds logged within `[from, to]` in each calendar week,
+/// keyed by the ISO date string of the week's Monday. Days of a week outside the range
+/// are not counted.
+#[tauri::command]
+pub fn get_report_by_week(
+    state: State<AppState>,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<HashMap<String, HashMap<String, u64>>, String> {
+    let report = state.report_by_week(from, to)?;
+    Ok(report
+        .into_iter()
+        .map(|(name, weeks)| {
+            let weeks = weeks
+                .into_iter()
+                .map(|(week_start, duration)| (week_start.format("%Y-%m-%d").to_string(), duration.as_secs()))
+                .collect();
+            (name, weeks)
+        })
+        .collect())
+}
+
+/// Adds a tag to a task.
+#[tauri::command]
+pub fn add_tag(state: State<AppState>, name: String, tag: String) -> Result<(), String> {
+    let trimmed_tag = tag.trim().to_string();
+
+    if trimmed_tag.is_empty() {
+        return Err("Tag cannot be empty".to_string());
+    }
+
+    state.add_tag(&name, trimmed_tag)
+}
+
+/// Retrieves tasks matching the given tag, along with their ancestors.
+#[tauri::command]
+pub fn get_tasks_by_tag(state: State<AppState>, tag: String) -> Result<Vec<TaskDto>, String> {
+    state.tasks_by_tag(&tag)
+}
+
 /// Adds a new task with the given name.
 #[tauri::command]
 pub fn add_task(state: State<AppState>, name: String) -> Result<(), String> {
@@ -49,14 +158,20 @@ pub fn reset_task(state: State<AppState>, name: String) -> Result<(), String> {
     state.reset_task(&name)
 }
 
+/// Sets the priority of the specified task.
+#[tauri::command]
+pub fn set_priority(state: State<AppState>, name: String, priority: Priority) -> Result<(), String> {
+    state.set_priority(&name, priority)
+}
+
 /// Deletes the specified task.
 #[tauri::command]
 pub fn delete_task(state: State<AppState>, name: String) -> Result<(), String> {
     state.delete_task(&name)
 }
 
-/// Exports all tasks to a text file at the specified path.
+/// Exports all tasks to the specified path in the given format.
 #[tauri::command]
-pub fn export_tasks(state: State<AppState>, path: String) -> Result<(), String> {
-    state.export_tasks(&path)
+pub fn export_tasks(state: State<AppState>, path: String, format: ExportFormat) -> Result<(), String> {
+    state.export_tasks(&path, format)
 }
\ No newline at end of file
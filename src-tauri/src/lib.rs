@@ -30,6 +30,18 @@ pub fn run() {
             commands::reset_task,
             commands::delete_task,
             commands::export_tasks,
+            commands::import_tasks,
+            commands::add_subtask,
+            commands::set_parent,
+            commands::add_dependency,
+            commands::remove_dependency,
+            commands::get_aggregated_time,
+            commands::get_topological_order,
+            commands::get_report_by_day,
+            commands::get_report_by_week,
+            commands::add_tag,
+            commands::get_tasks_by_tag,
+            commands::set_priority,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
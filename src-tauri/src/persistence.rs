@@ -1,8 +1,24 @@
-use crate::task::Task;
+use crate::task::{Task, TaskDto};
+use serde::Deserialize;
 use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
+/// File format to export tasks to.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Txt,
+    Csv,
+    Json,
+    Yaml,
+    Markdown,
+}
+
+/// Number of rotated backups of the save file to retain; the oldest is pruned once
+/// a save would exceed this.
+const MAX_BACKUPS: usize = 5;
+
 /// Handles persistence of tasks to and from disk.
 pub struct Persistence;
 
@@ -15,10 +31,10 @@ impl Persistence {
     }
 
     /// Saves tasks to the persistent storage file.
-    /// 
+    ///
     /// # Arguments
     /// * `tasks` - HashMap of task name to Task
-    /// 
+    ///
     /// # Returns
     /// Result indicating success or error message
     pub fn save_tasks(tasks: &HashMap<String, Task>) -> Result<(), String> {
@@ -30,19 +46,139 @@ impl Persistence {
                 .map_err(|e| format!("Failed to create config directory: {}", e))?;
         }
 
-        let json_data = serde_json::to_string_pretty(tasks)
+        let json_data = Self::to_canonical_json(tasks)
             .map_err(|e| format!("Failed to serialize tasks: {}", e))?;
 
-        fs::write(&save_path, json_data)
-            .map_err(|e| format!("Failed to save tasks: {}", e))?;
+        Self::write_atomically(&save_path, &json_data)
+            .map_err(|e| format!("Failed to save tasks: {}", e))
+    }
+
+    /// Serializes tasks with sorted map keys, so repeated saves of unchanged state
+    /// produce byte-identical output regardless of `HashMap` iteration order. This keeps
+    /// the save file diffable in git and comparable across machines for a future sync feature.
+    fn to_canonical_json(tasks: &HashMap<String, Task>) -> serde_json::Result<String> {
+        let canonical: std::collections::BTreeMap<&String, &Task> = tasks.iter().collect();
+        serde_json::to_string_pretty(&canonical)
+    }
+
+    /// Writes `data` to `path` crash-safely. The new contents are written to a temporary
+    /// file in the same directory and fsynced, the existing good file (if any) is rotated
+    /// into a timestamped backup, and only then is the temp file renamed over `path`.
+    /// `fs::rename` is atomic within a filesystem, so a crash mid-write can never leave
+    /// `path` truncated or partially written.
+    fn write_atomically(path: &std::path::Path, data: &str) -> std::io::Result<()> {
+        use std::io::Write;
+
+        let tmp_path = path.with_extension("json.tmp");
+        {
+            let mut tmp_file = fs::File::create(&tmp_path)?;
+            tmp_file.write_all(data.as_bytes())?;
+            tmp_file.sync_all()?;
+        }
+
+        if path.exists() {
+            Self::rotate_backup(path)?;
+        }
+
+        fs::rename(&tmp_path, path)?;
+
+        // A failure here must not fail the save: the rename already committed good data
+        // to `path`. If the sidecar can't be (re)written, remove it instead of leaving it
+        // describing the old contents, so `verify_integrity` falls back to its "no sidecar"
+        // trusted path rather than flagging freshly-saved data as corrupted.
+        if let Err(e) = Self::write_hash_sidecar(path, data) {
+            eprintln!("Warning: failed to write integrity sidecar for {}: {}", path.display(), e);
+            let _ = fs::remove_file(Self::hash_sidecar_path(path));
+        }
 
         Ok(())
     }
 
-    /// Loads tasks from the persistent storage file.
-    /// 
+    /// Returns the path of the BLAKE3 digest sidecar for a save file.
+    fn hash_sidecar_path(path: &std::path::Path) -> PathBuf {
+        path.with_extension("json.blake3")
+    }
+
+    /// Writes the hex-encoded BLAKE3 digest of `data` to `path`'s sidecar file, so
+    /// `load_tasks` can detect a save file that was corrupted or partially rewritten
+    /// after the fact.
+    fn write_hash_sidecar(path: &std::path::Path, data: &str) -> std::io::Result<()> {
+        let digest = blake3::hash(data.as_bytes()).to_hex();
+        fs::write(Self::hash_sidecar_path(path), digest.as_str())
+    }
+
+    /// Verifies `data` against the BLAKE3 digest recorded in its sidecar, if one exists.
+    /// A missing sidecar (e.g. a file saved before this feature existed) is treated as
+    /// trusted rather than a failure.
+    fn verify_integrity(path: &std::path::Path, data: &str) -> Result<(), String> {
+        let sidecar = Self::hash_sidecar_path(path);
+        if !sidecar.exists() {
+            return Ok(());
+        }
+
+        let expected = fs::read_to_string(&sidecar).map_err(|e| e.to_string())?;
+        let actual = blake3::hash(data.as_bytes()).to_hex();
+        if expected.trim() != actual.as_str() {
+            return Err("data file integrity check failed: BLAKE3 digest mismatch".to_string());
+        }
+        Ok(())
+    }
+
+    /// Copies the current good file to a timestamped backup alongside it, then prunes
+    /// backups beyond `MAX_BACKUPS`, oldest first.
+    ///
+    /// Several saves can land within the same wall-clock second (a few quick clicks each
+    /// trigger their own `save()`), so the timestamp alone isn't a unique suffix: if it
+    /// collided, `fs::copy` would overwrite the previous backup with the file it was meant
+    /// to protect. Bump the suffix by one past any existing backup with the same timestamp
+    /// to keep every rotation distinct.
+    fn rotate_backup(path: &std::path::Path) -> std::io::Result<()> {
+        let parent = path.parent().unwrap_or_else(|| std::path::Path::new("."));
+        let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("data.json");
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let prefix = format!("{}.bak.{}", file_name, timestamp);
+        let mut suffix = 0u32;
+        let mut backup_path = parent.join(&prefix);
+        while backup_path.exists() {
+            suffix += 1;
+            backup_path = parent.join(format!("{}.{}", prefix, suffix));
+        }
+
+        fs::copy(path, &backup_path)?;
+        Self::prune_backups(parent, file_name)
+    }
+
+    /// Removes the oldest backups of `file_name` in `parent` until at most `MAX_BACKUPS` remain.
+    fn prune_backups(parent: &std::path::Path, file_name: &str) -> std::io::Result<()> {
+        let prefix = format!("{}.bak.", file_name);
+        let mut backups: Vec<_> = fs::read_dir(parent)?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| {
+                p.file_name()
+                    .and_then(|n| n.to_str())
+                    .map(|n| n.starts_with(&prefix))
+                    .unwrap_or(false)
+            })
+            .collect();
+        backups.sort();
+        while backups.len() > MAX_BACKUPS {
+            let oldest = backups.remove(0);
+            let _ = fs::remove_file(oldest);
+        }
+        Ok(())
+    }
+
+    /// Loads tasks from the persistent storage file, falling back to the latest backup
+    /// if the main file fails its integrity check or fails to deserialize. A missing main
+    /// file yields an empty map; backups are not consulted in that case.
+    ///
     /// # Returns
-    /// HashMap of task name to Task, or empty HashMap if file doesn't exist
+    /// HashMap of task name to Task, or empty HashMap if no usable file exists
     pub fn load_tasks() -> HashMap<String, Task> {
         let save_path = Self::get_save_path();
 
@@ -50,23 +186,50 @@ impl Persistence {
             return HashMap::new();
         }
 
-        match fs::read_to_string(&save_path) {
-            Ok(json_data) => {
-                match serde_json::from_str::<HashMap<String, Task>>(&json_data) {
-                    Ok(tasks) => tasks,
-                    Err(e) => {
-                        eprintln!("Failed to deserialize tasks: {}", e);
+        match Self::load_from(&save_path) {
+            Ok(tasks) => tasks,
+            Err(e) => {
+                eprintln!("Failed to load tasks from {}: {}", save_path.display(), e);
+                match Self::latest_backup(&save_path).and_then(|p| Self::load_from(&p).ok()) {
+                    Some(tasks) => {
+                        eprintln!("Recovered {} tasks from latest backup", tasks.len());
+                        tasks
+                    }
+                    None => {
+                        eprintln!("No usable backup found, starting fresh");
                         HashMap::new()
                     }
                 }
             }
-            Err(e) => {
-                eprintln!("Failed to read tasks file: {}", e);
-                HashMap::new()
-            }
         }
     }
 
+    /// Reads and deserializes tasks from a specific file, rejecting it if its contents
+    /// don't match the recorded BLAKE3 digest.
+    fn load_from(path: &std::path::Path) -> Result<HashMap<String, Task>, String> {
+        let json_data = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        Self::verify_integrity(path, &json_data)?;
+        serde_json::from_str(&json_data).map_err(|e| e.to_string())
+    }
+
+    /// Returns the most recent backup of `path`, if any exist.
+    fn latest_backup(path: &std::path::Path) -> Option<PathBuf> {
+        let parent = path.parent()?;
+        let file_name = path.file_name()?.to_str()?;
+        let prefix = format!("{}.bak.", file_name);
+        fs::read_dir(parent)
+            .ok()?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| {
+                p.file_name()
+                    .and_then(|n| n.to_str())
+                    .map(|n| n.starts_with(&prefix))
+                    .unwrap_or(false)
+            })
+            .max()
+    }
+
     /// Exports tasks to a text file at the specified path.
     /// 
     /// # Arguments
@@ -108,6 +271,125 @@ impl Persistence {
 
         Ok(())
     }
+
+    /// Exports tasks to the given path in the requested format.
+    ///
+    /// # Arguments
+    /// * `tasks` - HashMap of task name to Task
+    /// * `path` - Path where the export file should be written
+    /// * `format` - File format to write
+    ///
+    /// # Returns
+    /// Result indicating success or error message
+    pub fn export_tasks(tasks: &HashMap<String, Task>, path: &str, format: ExportFormat) -> Result<(), String> {
+        match format {
+            ExportFormat::Txt => Self::export_to_txt(tasks, path),
+            ExportFormat::Csv => Self::export_to_csv(tasks, path),
+            ExportFormat::Json => Self::export_to_json(tasks, path),
+            ExportFormat::Yaml => Self::export_to_yaml(tasks, path),
+            ExportFormat::Markdown => Self::export_to_markdown(tasks, path),
+        }
+    }
+
+    /// Exports tasks as CSV, with one row per task and one row per time entry for
+    /// spreadsheet pivoting, distinguished by the `record_type` column.
+    pub fn export_to_csv(tasks: &HashMap<String, Task>, path: &str) -> Result<(), String> {
+        use std::io::Write;
+
+        let mut file = fs::File::create(path)
+            .map_err(|e| format!("Failed to create export file: {}", e))?;
+
+        writeln!(
+            file,
+            "record_type,name,total_seconds,formatted_time,created_at,is_running,logged_date,entry_seconds"
+        )
+        .map_err(|e| format!("Failed to write to export file: {}", e))?;
+
+        let mut sorted_tasks: Vec<_> = tasks.iter().collect();
+        sorted_tasks.sort_by(|a, b| a.1.created_at.cmp(&b.1.created_at));
+
+        for (name, task) in sorted_tasks {
+            let current_time = task.get_current_time();
+            writeln!(
+                file,
+                "task,{},{},{},{},{},,",
+                Self::csv_escape(name),
+                current_time.as_secs(),
+                Task::format_duration(current_time),
+                task.created_at.format("%Y-%m-%d %H:%M:%S"),
+                task.is_running,
+            )
+            .map_err(|e| format!("Failed to write to export file: {}", e))?;
+
+            for entry in &task.entries {
+                writeln!(
+                    file,
+                    "entry,{},,,,,{},{}",
+                    Self::csv_escape(name),
+                    entry.logged_date,
+                    entry.duration.as_secs(),
+                )
+                .map_err(|e| format!("Failed to write to export file: {}", e))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Exports tasks as a JSON array, mirroring the DTO sent to the frontend.
+    pub fn export_to_json(tasks: &HashMap<String, Task>, path: &str) -> Result<(), String> {
+        let dtos = TaskDto::build_list(tasks);
+        let json_data = serde_json::to_string_pretty(&dtos)
+            .map_err(|e| format!("Failed to serialize tasks: {}", e))?;
+        fs::write(path, json_data).map_err(|e| format!("Failed to write to export file: {}", e))
+    }
+
+    /// Exports tasks as YAML, mirroring the DTO sent to the frontend.
+    pub fn export_to_yaml(tasks: &HashMap<String, Task>, path: &str) -> Result<(), String> {
+        let dtos = TaskDto::build_list(tasks);
+        let yaml_data = serde_yaml::to_string(&dtos)
+            .map_err(|e| format!("Failed to serialize tasks: {}", e))?;
+        fs::write(path, yaml_data).map_err(|e| format!("Failed to write to export file: {}", e))
+    }
+
+    /// Exports tasks as a GitHub-flavored Markdown table, one row per task.
+    pub fn export_to_markdown(tasks: &HashMap<String, Task>, path: &str) -> Result<(), String> {
+        use std::io::Write;
+
+        let mut file = fs::File::create(path)
+            .map_err(|e| format!("Failed to create export file: {}", e))?;
+
+        writeln!(file, "| Name | Total Time | Created | Running |")
+            .map_err(|e| format!("Failed to write to export file: {}", e))?;
+        writeln!(file, "| --- | --- | --- | --- |")
+            .map_err(|e| format!("Failed to write to export file: {}", e))?;
+
+        let mut sorted_tasks: Vec<_> = tasks.iter().collect();
+        sorted_tasks.sort_by(|a, b| a.1.created_at.cmp(&b.1.created_at));
+
+        for (name, task) in sorted_tasks {
+            writeln!(
+                file,
+                "| {} | {} | {} | {} |",
+                name,
+                Task::format_duration(task.get_current_time()),
+                task.created_at.format("%Y-%m-%d %H:%M:%S"),
+                task.is_running,
+            )
+            .map_err(|e| format!("Failed to write to export file: {}", e))?;
+        }
+
+        Ok(())
+    }
+
+    /// Quotes a CSV field if it contains a comma, quote, or newline, escaping embedded quotes.
+    fn csv_escape(value: &str) -> String {
+        if value.contains(',') || value.contains('"') || value.contains('\n') {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    }
 }
 
 #[cfg(test)]
@@ -133,4 +415,39 @@ mod tests {
         let loaded = Persistence::load_tasks();
         assert!(loaded.contains_key("Test Task"));
     }
+
+    #[test]
+    fn test_write_atomically_rotates_backup() {
+        let temp_dir = env::temp_dir().join("time_tracker_atomic_test");
+        // Start from an empty directory so backups left by an earlier run aren't counted.
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+        let path = temp_dir.join("data.json");
+        let _ = fs::remove_file(&path);
+
+        Persistence::write_atomically(&path, "{\"a\":1}").unwrap();
+        Persistence::write_atomically(&path, "{\"a\":2}").unwrap();
+        Persistence::write_atomically(&path, "{\"a\":3}").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"a\":3}");
+
+        let mut backups: Vec<_> = fs::read_dir(&temp_dir)
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().starts_with("data.json.bak."))
+            .map(|e| e.path())
+            .collect();
+        backups.sort();
+
+        // Three writes within the same second must still produce two distinct backups
+        // (the pre-rotation contents of each of the first two writes), not one overwritten
+        // by the other.
+        assert_eq!(backups.len(), 2);
+        let contents: Vec<String> = backups
+            .iter()
+            .map(|p| fs::read_to_string(p).unwrap())
+            .collect();
+        assert!(contents.contains(&"{\"a\":1}".to_string()));
+        assert!(contents.contains(&"{\"a\":2}".to_string()));
+    }
 }
\ No newline at end of file
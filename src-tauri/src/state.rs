@@ -1,7 +1,8 @@
-use crate::persistence::Persistence;
-use crate::task::Task;
+use crate::persistence::{ExportFormat, Persistence};
+use crate::task::{Priority, Task, TaskDto};
 use std::collections::HashMap;
 use std::sync::Mutex;
+use std::time::Duration;
 
 /// Thread-safe application state holding all tasks.
 pub struct AppState {
@@ -124,18 +125,530 @@ impl AppState {
         Ok(())
     }
 
-    /// Exports all tasks to a text file.
-    /// 
+    /// Sets the priority of the specified task.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the task to update
+    /// * `priority` - New priority level
+    ///
+    /// # Returns
+    /// Result with unit on success, or error if task not found
+    pub fn set_priority(&self, name: &str, priority: Priority) -> Result<(), String> {
+        let mut tasks = self.tasks.lock()
+            .map_err(|_| "Failed to acquire lock")?;
+
+        let task = tasks.get_mut(name)
+            .ok_or_else(|| format!("Task '{}' not found", name))?;
+
+        task.priority = priority;
+        self.save(&tasks)?;
+        Ok(())
+    }
+
+    /// Creates a new task nested under an existing parent task.
+    ///
+    /// # Arguments
+    /// * `parent_name` - Name of the existing parent task
+    /// * `child_name` - Name of the new subtask to create
+    ///
+    /// # Returns
+    /// Result with unit on success, or error if the parent is missing or the child already exists
+    pub fn add_subtask(&self, parent_name: &str, child_name: String) -> Result<(), String> {
+        let mut tasks = self.tasks.lock()
+            .map_err(|_| "Failed to acquire lock")?;
+
+        if !tasks.contains_key(parent_name) {
+            return Err(format!("Task '{}' not found", parent_name));
+        }
+        if tasks.contains_key(&child_name) {
+            return Err(format!("Task '{}' already exists", child_name));
+        }
+
+        let mut child = Task::new(child_name.clone());
+        child.parent = Some(parent_name.to_string());
+        tasks.insert(child_name, child);
+        self.save(&tasks)?;
+        Ok(())
+    }
+
+    /// Sets or clears the parent of a task, rejecting edits that would create a cycle.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the task to reparent
+    /// * `parent` - New parent name, or `None` to make it a root task
+    ///
+    /// # Returns
+    /// Result with unit on success, or error if either task is missing or a cycle would result
+    pub fn set_parent(&self, name: &str, parent: Option<String>) -> Result<(), String> {
+        let mut tasks = self.tasks.lock()
+            .map_err(|_| "Failed to acquire lock")?;
+
+        if !tasks.contains_key(name) {
+            return Err(format!("Task '{}' not found", name));
+        }
+
+        if let Some(ref parent_name) = parent {
+            if parent_name == name {
+                return Err("A task cannot be its own parent".to_string());
+            }
+            if !tasks.contains_key(parent_name) {
+                return Err(format!("Task '{}' not found", parent_name));
+            }
+
+            // Walk up from the proposed parent; if we reach `name`, the edit would create a cycle.
+            let mut current = tasks.get(parent_name).and_then(|t| t.parent.clone());
+            while let Some(ancestor) = current {
+                if ancestor == name {
+                    return Err(format!(
+                        "Setting '{}' as the parent of '{}' would create a cycle",
+                        parent_name, name
+                    ));
+                }
+                current = tasks.get(&ancestor).and_then(|t| t.parent.clone());
+            }
+        }
+
+        tasks.get_mut(name).unwrap().parent = parent;
+        self.save(&tasks)?;
+        Ok(())
+    }
+
+    /// Loads a task database from another save file and merges it into the live state.
+    /// Tasks present only on one side are kept as-is; tasks present on both are merged
+    /// field-by-field with "last modified wins", and sessions are unioned (deduplicated by
+    /// `(start, end)`) rather than summed, so re-importing the same file is idempotent.
+    ///
+    /// # Arguments
+    /// * `path` - Path to a JSON file in this crate's save format
+    ///
+    /// # Returns
+    /// Result with unit on success, or error if the file can't be read or parsed
+    pub fn merge_from(&self, path: &str) -> Result<(), String> {
+        let json_data = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read '{}': {}", path, e))?;
+        let incoming: HashMap<String, Task> = serde_json::from_str(&json_data)
+            .map_err(|e| format!("Failed to parse '{}': {}", path, e))?;
+
+        let mut tasks = self.tasks.lock()
+            .map_err(|_| "Failed to acquire lock")?;
+
+        for (name, other) in incoming {
+            match tasks.get_mut(&name) {
+                None => {
+                    tasks.insert(name, other);
+                }
+                Some(existing) => Self::merge_task(existing, &other),
+            }
+        }
+
+        // Each task's `depends_on` was merged independently above, so two devices that
+        // added different edges between the same pair of tasks can combine into a cycle
+        // that neither side had on its own. Re-validate the whole graph afterwards the
+        // same way `add_dependency` validates a single edge, dropping whichever edges
+        // would create one.
+        Self::break_dependency_cycles(&mut tasks);
+
+        // Same reasoning applies to `parent`: two devices that each reparented a different
+        // task toward the other can sync into a parent cycle, which would make every
+        // unconditional tree walk over the map (`TaskDto::build_list`, `aggregate_time`)
+        // recurse forever.
+        Self::break_parent_cycles(&mut tasks);
+
+        self.save(&tasks)?;
+        Ok(())
+    }
+
+    /// Merges `other` into `existing` in place: sessions are unioned by `(start, end)`,
+    /// and each newly-added session's time is added on top of `total_time`/`entries`
+    /// rather than rebuilding both from `sessions` alone, since `sessions` isn't guaranteed
+    /// to cover a task's whole history (a save file from before `sessions` existed, or one
+    /// hand-edited per the file-watch workflow, has none) and rebuilding would silently
+    /// erase that unrepresented time. Every other field is taken from whichever side was
+    /// modified more recently.
+    fn merge_task(existing: &mut Task, other: &Task) {
+        for session in &other.sessions {
+            let already_present = existing
+                .sessions
+                .iter()
+                .any(|s| s.start == session.start && s.end == session.end);
+            if !already_present {
+                existing.sessions.push(session.clone());
+                existing.total_time += (session.end - session.start).to_std().unwrap_or_default();
+                Task::log_session(&mut existing.entries, session.start, session.end);
+            }
+        }
+
+        if other.modified_at > existing.modified_at {
+            existing.tags = other.tags.clone();
+            existing.parent = other.parent.clone();
+            existing.depends_on = other.depends_on.clone();
+            existing.priority = other.priority;
+            existing.modified_at = other.modified_at;
+        }
+    }
+
+    /// Rebuilds every task's `depends_on` list from scratch, re-adding each edge only if it
+    /// doesn't create a cycle given the edges already re-added, using the same reachability
+    /// check as `add_dependency`. Tasks are visited in sorted order and edges within a task
+    /// in their original order, so the result is deterministic; whichever edge of a
+    /// conflicting pair was added first wins and the other is dropped.
+    fn break_dependency_cycles(tasks: &mut HashMap<String, Task>) {
+        let mut names: Vec<String> = tasks.keys().cloned().collect();
+        names.sort();
+
+        let mut candidate_edges = Vec::new();
+        for name in &names {
+            if let Some(t) = tasks.get(name) {
+                for dep in &t.depends_on {
+                    candidate_edges.push((name.clone(), dep.clone()));
+                }
+            }
+        }
+
+        for t in tasks.values_mut() {
+            t.depends_on.clear();
+        }
+
+        for (task, depends_on) in candidate_edges {
+            if task == depends_on || !tasks.contains_key(&depends_on) {
+                continue;
+            }
+
+            let mut visited = std::collections::HashSet::new();
+            let mut stack = vec![depends_on.clone()];
+            let mut creates_cycle = false;
+            while let Some(current) = stack.pop() {
+                if current == task {
+                    creates_cycle = true;
+                    break;
+                }
+                if !visited.insert(current.clone()) {
+                    continue;
+                }
+                if let Some(t) = tasks.get(&current) {
+                    stack.extend(t.depends_on.iter().cloned());
+                }
+            }
+
+            if !creates_cycle {
+                tasks.get_mut(&task).unwrap().depends_on.push(depends_on);
+            }
+        }
+    }
+
+    /// Rebuilds every task's `parent` link from scratch, re-adding each one only if it
+    /// doesn't create a cycle given the parents already re-added, using the same
+    /// ancestor-walk check `set_parent` uses for a single edit. Tasks are visited in
+    /// sorted order, so the result is deterministic; whichever side of a conflicting pair
+    /// is visited first wins and the other's `parent` link is dropped.
+    fn break_parent_cycles(tasks: &mut HashMap<String, Task>) {
+        let mut names: Vec<String> = tasks.keys().cloned().collect();
+        names.sort();
+
+        let candidate_parents: Vec<(String, String)> = names
+            .iter()
+            .filter_map(|name| {
+                tasks
+                    .get(name)
+                    .and_then(|t| t.parent.clone())
+                    .map(|parent| (name.clone(), parent))
+            })
+            .collect();
+
+        for t in tasks.values_mut() {
+            t.parent = None;
+        }
+
+        for (name, parent) in candidate_parents {
+            if name == parent || !tasks.contains_key(&parent) {
+                continue;
+            }
+
+            // Walk up from the proposed parent; if we reach `name`, the edit would create a cycle.
+            let mut current = tasks.get(&parent).and_then(|t| t.parent.clone());
+            let mut creates_cycle = false;
+            while let Some(ancestor) = current {
+                if ancestor == name {
+                    creates_cycle = true;
+                    break;
+                }
+                current = tasks.get(&ancestor).and_then(|t| t.parent.clone());
+            }
+
+            if !creates_cycle {
+                tasks.get_mut(&name).unwrap().parent = Some(parent);
+            }
+        }
+    }
+
+    /// Adds a dependency edge so `task` depends on `depends_on`, rejecting the edge if it
+    /// would create a cycle.
+    ///
+    /// # Arguments
+    /// * `task` - Name of the task gaining the dependency
+    /// * `depends_on` - Name of the task it should depend on
+    ///
+    /// # Returns
+    /// Result with unit on success, or error if either task is missing or a cycle would result
+    pub fn add_dependency(&self, task: &str, depends_on: &str) -> Result<(), String> {
+        let mut tasks = self.tasks.lock()
+            .map_err(|_| "Failed to acquire lock")?;
+
+        if task == depends_on {
+            return Err("A task cannot depend on itself".to_string());
+        }
+        if !tasks.contains_key(task) {
+            return Err(format!("Task '{}' not found", task));
+        }
+        if !tasks.contains_key(depends_on) {
+            return Err(format!("Task '{}' not found", depends_on));
+        }
+
+        // DFS from the proposed dependency; if it can reach `task`, the edge would create a cycle.
+        let mut visited = std::collections::HashSet::new();
+        let mut stack = vec![depends_on.to_string()];
+        while let Some(current) = stack.pop() {
+            if current == task {
+                return Err(format!(
+                    "Making '{}' depend on '{}' would create a cycle",
+                    task, depends_on
+                ));
+            }
+            if !visited.insert(current.clone()) {
+                continue;
+            }
+            if let Some(t) = tasks.get(&current) {
+                stack.extend(t.depends_on.iter().cloned());
+            }
+        }
+
+        let t = tasks.get_mut(task).unwrap();
+        if !t.depends_on.iter().any(|d| d == depends_on) {
+            t.depends_on.push(depends_on.to_string());
+        }
+        self.save(&tasks)?;
+        Ok(())
+    }
+
+    /// Removes a dependency edge from `task` to `depends_on`, if present.
+    ///
+    /// # Arguments
+    /// * `task` - Name of the task losing the dependency
+    /// * `depends_on` - Name of the dependency to remove
+    ///
+    /// # Returns
+    /// Result with unit on success, or error if the task is not found
+    pub fn remove_dependency(&self, task: &str, depends_on: &str) -> Result<(), String> {
+        let mut tasks = self.tasks.lock()
+            .map_err(|_| "Failed to acquire lock")?;
+
+        let t = tasks.get_mut(task)
+            .ok_or_else(|| format!("Task '{}' not found", task))?;
+
+        t.depends_on.retain(|d| d != depends_on);
+        self.save(&tasks)?;
+        Ok(())
+    }
+
+    /// Returns `name`'s own current time plus the current time of all transitive
+    /// dependencies, visiting each node once so diamond-shaped graphs aren't double-counted.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the task to aggregate
+    pub fn aggregated_time(&self, name: &str) -> Result<Duration, String> {
+        let tasks = self.tasks.lock()
+            .map_err(|_| "Failed to acquire lock")?;
+
+        if !tasks.contains_key(name) {
+            return Err(format!("Task '{}' not found", name));
+        }
+
+        let mut visited = std::collections::HashSet::new();
+        let mut stack = vec![name.to_string()];
+        let mut total = Duration::ZERO;
+        while let Some(current) = stack.pop() {
+            if !visited.insert(current.clone()) {
+                continue;
+            }
+            if let Some(t) = tasks.get(&current) {
+                total += t.get_current_time();
+                stack.extend(t.depends_on.iter().cloned());
+            }
+        }
+
+        Ok(total)
+    }
+
+    /// Returns all tasks in dependency order (a task's dependencies always precede it),
+    /// for use when exporting. The dependency graph is kept acyclic by `add_dependency`
+    /// and by `merge_from`'s `break_dependency_cycles` pass.
+    pub fn topological_order(&self) -> Result<Vec<String>, String> {
+        let tasks = self.tasks.lock()
+            .map_err(|_| "Failed to acquire lock")?;
+
+        let mut order = Vec::with_capacity(tasks.len());
+        let mut visited = std::collections::HashSet::new();
+
+        fn visit(
+            name: &str,
+            tasks: &HashMap<String, Task>,
+            visited: &mut std::collections::HashSet<String>,
+            order: &mut Vec<String>,
+        ) {
+            if !visited.insert(name.to_string()) {
+                return;
+            }
+            if let Some(t) = tasks.get(name) {
+                for dep in &t.depends_on {
+                    visit(dep, tasks, visited, order);
+                }
+            }
+            order.push(name.to_string());
+        }
+
+        let mut names: Vec<&String> = tasks.keys().collect();
+        names.sort();
+        for name in names {
+            visit(name, &tasks, &mut visited, &mut order);
+        }
+
+        Ok(order)
+    }
+
+    /// Buckets every task's sessions into calendar days within `[from, to]`, splitting a
+    /// session that crosses midnight at the day boundary.
+    ///
+    /// # Arguments
+    /// * `from` - First day to include, inclusive
+    /// * `to` - Last day to include, inclusive
+    ///
+    /// # Returns
+    /// A map of task name to a map of day to total duration logged that day
+    pub fn report_by_day(
+        &self,
+        from: chrono::NaiveDate,
+        to: chrono::NaiveDate,
+    ) -> Result<HashMap<String, HashMap<chrono::NaiveDate, Duration>>, String> {
+        let tasks = self.tasks.lock()
+            .map_err(|_| "Failed to acquire lock")?;
+
+        let mut report = HashMap::new();
+        for (name, task) in tasks.iter() {
+            let mut by_day: HashMap<chrono::NaiveDate, Duration> = HashMap::new();
+            for session in &task.sessions {
+                for (day, duration) in Task::split_by_day(session.start, session.end) {
+                    if day >= from && day <= to {
+                        *by_day.entry(day).or_insert(Duration::ZERO) += duration;
+                    }
+                }
+            }
+            if !by_day.is_empty() {
+                report.insert(name.clone(), by_day);
+            }
+        }
+        Ok(report)
+    }
+
+    /// Buckets every task's sessions into calendar weeks (keyed by the Monday each week
+    /// starts on) within `[from, to]`.
+    ///
+    /// # Arguments
+    /// * `from` - First day to include, inclusive
+    /// * `to` - Last day to include, inclusive
+    ///
+    /// # Returns
+    /// A map of task name to a map of week-start date to total duration logged that week
+    pub fn report_by_week(
+        &self,
+        from: chrono::NaiveDate,
+        to: chrono::NaiveDate,
+    ) -> Result<HashMap<String, HashMap<chrono::NaiveDate, Duration>>, String> {
+        use chrono::Datelike;
+
+        let daily = self.report_by_day(from, to)?;
+        let mut weekly = HashMap::new();
+        for (name, days) in daily {
+            let mut by_week: HashMap<chrono::NaiveDate, Duration> = HashMap::new();
+            for (day, duration) in days {
+                let week_start = day - chrono::Duration::days(day.weekday().num_days_from_monday() as i64);
+                *by_week.entry(week_start).or_insert(Duration::ZERO) += duration;
+            }
+            weekly.insert(name, by_week);
+        }
+        Ok(weekly)
+    }
+
+    /// Adds a tag to a task, if it isn't already present.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the task to tag
+    /// * `tag` - Tag to add
+    ///
+    /// # Returns
+    /// Result with unit on success, or error if the task is not found
+    pub fn add_tag(&self, name: &str, tag: String) -> Result<(), String> {
+        let mut tasks = self.tasks.lock()
+            .map_err(|_| "Failed to acquire lock")?;
+
+        let task = tasks.get_mut(name)
+            .ok_or_else(|| format!("Task '{}' not found", name))?;
+
+        if !task.tags.contains(&tag) {
+            task.tags.push(tag);
+        }
+
+        self.save(&tasks)?;
+        Ok(())
+    }
+
+    /// Returns DTOs for every task matching the given tag, plus their ancestors so the
+    /// hierarchy stays intact when the caller renders a filtered tree.
+    ///
+    /// # Arguments
+    /// * `tag` - Tag to filter by
+    pub fn tasks_by_tag(&self, tag: &str) -> Result<Vec<TaskDto>, String> {
+        let tasks = self.tasks.lock()
+            .map_err(|_| "Failed to acquire lock")?;
+
+        let matching: Vec<String> = tasks
+            .iter()
+            .filter(|(_, task)| task.tags.iter().any(|t| t == tag))
+            .map(|(name, _)| name.clone())
+            .collect();
+
+        let mut visible: std::collections::HashSet<String> = std::collections::HashSet::new();
+        for name in &matching {
+            let mut current = Some(name.clone());
+            while let Some(n) = current {
+                if !visible.insert(n.clone()) {
+                    break;
+                }
+                current = tasks.get(&n).and_then(|t| t.parent.clone());
+            }
+        }
+
+        let mut dtos: Vec<TaskDto> = visible
+            .iter()
+            .filter_map(|name| tasks.get(name))
+            .map(|task| TaskDto::with_aggregate(task, &tasks))
+            .collect();
+        dtos.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        Ok(dtos)
+    }
+
+    /// Exports all tasks to a file in the requested format.
+    ///
     /// # Arguments
     /// * `path` - Path where the export file should be written
-    /// 
+    /// * `format` - File format to write
+    ///
     /// # Returns
     /// Result with unit on success, or error message on failure
-    pub fn export_tasks(&self, path: &str) -> Result<(), String> {
+    pub fn export_tasks(&self, path: &str, format: ExportFormat) -> Result<(), String> {
         let tasks = self.tasks.lock()
             .map_err(|_| "Failed to acquire lock")?;
 
-        Persistence::export_to_txt(&tasks, path)
+        Persistence::export_tasks(&tasks, path, format)
     }
 }
 
@@ -169,7 +682,7 @@ mod tests {
     #[test]
     fn test_duplicate_task_error() {
         let state = AppState::new();
-        
+
         // Clear and add a task
         {
             let mut tasks = state.tasks.lock().unwrap();
@@ -180,4 +693,157 @@ mod tests {
         let result = state.add_task("Duplicate".to_string());
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_add_dependency_rejects_cycle() {
+        let state = AppState::new();
+        {
+            let mut tasks = state.tasks.lock().unwrap();
+            tasks.clear();
+            tasks.insert("A".to_string(), Task::new("A".to_string()));
+            tasks.insert("B".to_string(), Task::new("B".to_string()));
+        }
+
+        assert!(state.add_dependency("A", "B").is_ok());
+        assert!(state.add_dependency("B", "A").is_err());
+    }
+
+
+    #[test]
+    fn test_merge_from_is_idempotent() {
+        let state = AppState::new();
+        {
+            let mut tasks = state.tasks.lock().unwrap();
+            tasks.clear();
+        }
+
+        let mut incoming = HashMap::new();
+        incoming.insert("Imported".to_string(), Task::new("Imported".to_string()));
+        let temp_path = std::env::temp_dir().join("time_tracker_merge_idempotent_test.json");
+        std::fs::write(&temp_path, serde_json::to_string(&incoming).unwrap()).unwrap();
+
+        state.merge_from(temp_path.to_str().unwrap()).unwrap();
+        let snapshot_after_first = {
+            let tasks = state.tasks.lock().unwrap();
+            let t = tasks.get("Imported").unwrap();
+            (tasks.len(), t.total_time, t.sessions.len(), t.modified_at)
+        };
+
+        state.merge_from(temp_path.to_str().unwrap()).unwrap();
+        let snapshot_after_second = {
+            let tasks = state.tasks.lock().unwrap();
+            let t = tasks.get("Imported").unwrap();
+            (tasks.len(), t.total_time, t.sessions.len(), t.modified_at)
+        };
+
+        assert_eq!(snapshot_after_first, snapshot_after_second);
+
+        let _ = std::fs::remove_file(&temp_path);
+    }
+
+    #[test]
+    fn test_merge_from_breaks_introduced_cycle() {
+        let state = AppState::new();
+        {
+            let mut tasks = state.tasks.lock().unwrap();
+            tasks.clear();
+            let mut a = Task::new("A".to_string());
+            a.depends_on.push("B".to_string());
+            tasks.insert("A".to_string(), a);
+            tasks.insert("B".to_string(), Task::new("B".to_string()));
+        }
+
+        // The incoming file adds the opposite edge (B depends on A), which together with
+        // the local A -> B edge would form a cycle.
+        let mut incoming = HashMap::new();
+        let mut b = Task::new("B".to_string());
+        b.depends_on.push("A".to_string());
+        incoming.insert("B".to_string(), b);
+        let temp_path = std::env::temp_dir().join("time_tracker_merge_cycle_test.json");
+        std::fs::write(&temp_path, serde_json::to_string(&incoming).unwrap()).unwrap();
+
+        state.merge_from(temp_path.to_str().unwrap()).unwrap();
+
+        {
+            let tasks = state.tasks.lock().unwrap();
+            let a_depends_on_b = tasks["A"].depends_on.contains(&"B".to_string());
+            let b_depends_on_a = tasks["B"].depends_on.contains(&"A".to_string());
+            assert!(
+                !(a_depends_on_b && b_depends_on_a),
+                "both directions of the edge survived the merge, leaving a cycle"
+            );
+        }
+
+        let _ = std::fs::remove_file(&temp_path);
+    }
+
+    #[test]
+    fn test_merge_from_breaks_introduced_parent_cycle() {
+        let state = AppState::new();
+        {
+            let mut tasks = state.tasks.lock().unwrap();
+            tasks.clear();
+            let mut a = Task::new("A".to_string());
+            a.parent = Some("B".to_string());
+            tasks.insert("A".to_string(), a);
+            tasks.insert("B".to_string(), Task::new("B".to_string()));
+        }
+
+        // The incoming file reparents B under A, which together with the local A's
+        // parent being B would form a cycle.
+        let mut incoming = HashMap::new();
+        let mut b = Task::new("B".to_string());
+        b.parent = Some("A".to_string());
+        incoming.insert("B".to_string(), b);
+        let temp_path = std::env::temp_dir().join("time_tracker_merge_parent_cycle_test.json");
+        std::fs::write(&temp_path, serde_json::to_string(&incoming).unwrap()).unwrap();
+
+        state.merge_from(temp_path.to_str().unwrap()).unwrap();
+
+        {
+            let tasks = state.tasks.lock().unwrap();
+            let a_parent_is_b = tasks["A"].parent.as_deref() == Some("B");
+            let b_parent_is_a = tasks["B"].parent.as_deref() == Some("A");
+            assert!(
+                !(a_parent_is_b && b_parent_is_a),
+                "both directions of the parent link survived the merge, leaving a cycle"
+            );
+        }
+
+        let _ = std::fs::remove_file(&temp_path);
+    }
+
+    #[test]
+    fn test_merge_preserves_time_not_backed_by_sessions() {
+        let state = AppState::new();
+        {
+            let mut tasks = state.tasks.lock().unwrap();
+            tasks.clear();
+
+            // Simulates a task saved before `sessions` existed (or hand-edited): real
+            // tracked time with no corresponding `TimeSession`.
+            let mut legacy = Task::new("Legacy".to_string());
+            legacy.total_time = Duration::from_secs(3600);
+            legacy.entries.push(crate::task::TimeEntry {
+                logged_date: chrono::Local::now().date_naive(),
+                duration: Duration::from_secs(3600),
+            });
+            tasks.insert("Legacy".to_string(), legacy);
+        }
+
+        let mut incoming = HashMap::new();
+        incoming.insert("Legacy".to_string(), Task::new("Legacy".to_string()));
+        let temp_path = std::env::temp_dir().join("time_tracker_merge_legacy_time_test.json");
+        std::fs::write(&temp_path, serde_json::to_string(&incoming).unwrap()).unwrap();
+
+        state.merge_from(temp_path.to_str().unwrap()).unwrap();
+
+        {
+            let tasks = state.tasks.lock().unwrap();
+            assert_eq!(tasks["Legacy"].total_time, Duration::from_secs(3600));
+            assert_eq!(tasks["Legacy"].entries.len(), 1);
+        }
+
+        let _ = std::fs::remove_file(&temp_path);
+    }
 }
\ No newline at end of file
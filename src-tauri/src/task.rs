@@ -1,6 +1,36 @@
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Datelike, Local, NaiveDate, TimeZone};
+
+/// Priority level used to highlight and sort tasks. Ordered `Low < Medium < High`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Priority {
+    #[default]
+    Low,
+    Medium,
+    High,
+}
+
+/// Total time from completed tracking sessions logged on a single calendar day.
+///
+/// `Task::log_session` merges every session of the same day into one entry, and splits a
+/// session that spans midnight into one entry per day, so `logged_date` always identifies
+/// a single local calendar day.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TimeEntry {
+    pub logged_date: NaiveDate,
+    #[serde(with = "duration_serde")]
+    pub duration: Duration,
+}
+
+/// A single completed start/stop interval, with exact timestamps. Unlike `TimeEntry`,
+/// a session is not split at day boundaries, so `AppState::report_by_day`/`report_by_week`
+/// re-split it on demand to bucket by whatever range is being reported on.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TimeSession {
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+}
 
 /// Represents a time-tracked task with accumulated time and state.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -8,11 +38,35 @@ pub struct Task {
     pub name: String,
     #[serde(with = "duration_serde")]
     pub total_time: Duration,
+    /// Per-day log of completed sessions, used for daily/weekly breakdowns.
+    #[serde(default)]
+    pub entries: Vec<TimeEntry>,
+    /// Exact start/end record of every completed session, kept alongside `entries` as the
+    /// source of truth for report queries over arbitrary date ranges.
+    #[serde(default)]
+    pub sessions: Vec<TimeSession>,
     #[serde(skip)]
     pub start_timestamp: Option<i64>,
     #[serde(skip)]
     pub is_running: bool,
     pub created_at: DateTime<Local>,
+    /// Free-form labels used to group and filter tasks.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Name of the task this one is nested under, if any.
+    #[serde(default)]
+    pub parent: Option<String>,
+    /// Names of tasks this one depends on. Distinct from `parent`: a parent/child edge
+    /// models UI nesting, while `depends_on` models a project dependency graph.
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// Priority used for highlighting and sorting.
+    #[serde(default)]
+    pub priority: Priority,
+    /// When this task was last changed by `start`/`stop`/`reset`, used to resolve
+    /// conflicts when merging state imported from another device.
+    #[serde(default = "Local::now")]
+    pub modified_at: DateTime<Local>,
 }
 
 impl Task {
@@ -21,9 +75,16 @@ impl Task {
         Self {
             name,
             total_time: Duration::ZERO,
+            entries: Vec::new(),
+            sessions: Vec::new(),
             start_timestamp: None,
             is_running: false,
             created_at: Local::now(),
+            tags: Vec::new(),
+            parent: None,
+            depends_on: Vec::new(),
+            priority: Priority::default(),
+            modified_at: Local::now(),
         }
     }
 
@@ -32,6 +93,7 @@ impl Task {
         if !self.is_running {
             self.start_timestamp = Some(Local::now().timestamp_millis());
             self.is_running = true;
+            self.modified_at = Local::now();
         }
     }
 
@@ -42,19 +104,76 @@ impl Task {
                 let now = Local::now().timestamp_millis();
                 let elapsed_millis = (now - start).max(0) as u64;
                 self.total_time += Duration::from_millis(elapsed_millis);
+
+                if let (Some(start_dt), Some(end_dt)) = (
+                    Local.timestamp_millis_opt(start).single(),
+                    Local.timestamp_millis_opt(now).single(),
+                ) {
+                    Self::log_session(&mut self.entries, start_dt, end_dt);
+                    self.sessions.push(TimeSession { start: start_dt, end: end_dt });
+                }
             }
             self.is_running = false;
             self.start_timestamp = None;
+            self.modified_at = Local::now();
+        }
+    }
+
+    /// Splits a session into one `TimeEntry` per calendar day it overlaps and merges
+    /// each into the existing entry for that day, if any.
+    pub(crate) fn log_session(entries: &mut Vec<TimeEntry>, start: DateTime<Local>, end: DateTime<Local>) {
+        for (logged_date, duration) in Self::split_by_day(start, end) {
+            match entries.iter_mut().find(|e| e.logged_date == logged_date) {
+                Some(entry) => entry.duration += duration,
+                None => entries.push(TimeEntry { logged_date, duration }),
+            }
         }
     }
 
+    /// Splits a `[start, end)` interval into one `(date, duration)` pair per calendar day
+    /// it overlaps, so a session crossing midnight is attributed to each day separately.
+    pub(crate) fn split_by_day(start: DateTime<Local>, end: DateTime<Local>) -> Vec<(NaiveDate, Duration)> {
+        let mut parts = Vec::new();
+        let mut segment_start = start;
+        while segment_start < end {
+            let next_midnight = (segment_start.date_naive() + chrono::Duration::days(1))
+                .and_hms_opt(0, 0, 0)
+                .and_then(|ndt| Local.from_local_datetime(&ndt).single());
+            let segment_end = match next_midnight {
+                Some(midnight) => end.min(midnight),
+                None => end,
+            };
+
+            if let Ok(duration) = (segment_end - segment_start).to_std() {
+                parts.push((segment_start.date_naive(), duration));
+            }
+
+            segment_start = segment_end;
+        }
+        parts
+    }
+
     /// Resets the task's accumulated time to zero.
     pub fn reset(&mut self) {
         self.stop();
         self.total_time = Duration::ZERO;
+        self.entries.clear();
+        self.sessions.clear();
+        self.modified_at = Local::now();
+    }
+
+    /// Sums the logged entries falling within the inclusive date range.
+    pub fn time_in_range(&self, from: NaiveDate, to: NaiveDate) -> Duration {
+        self.entries
+            .iter()
+            .filter(|e| e.logged_date >= from && e.logged_date <= to)
+            .fold(Duration::ZERO, |acc, e| acc + e.duration)
     }
 
-    /// Returns the current total time including any running session.
+    /// Returns the current total time including any running session: `total_time` plus the
+    /// live elapsed time. `total_time` can include time with no matching `TimeSession`
+    /// (e.g. from a save file written before `sessions` existed), so it is read directly
+    /// rather than summed from `sessions`.
     pub fn get_current_time(&self) -> Duration {
         let mut current_time = self.total_time;
         if self.is_running {
@@ -85,18 +204,74 @@ pub struct TaskDto {
     pub formatted_time: String,
     pub is_running: bool,
     pub created_at: String,
+    /// Per-day log of completed sessions (does not include the currently running session).
+    pub entries: Vec<TimeEntry>,
+    pub today_secs: u64,
+    pub week_secs: u64,
+    pub tags: Vec<String>,
+    pub parent: Option<String>,
+    /// Own time plus the recursively accumulated time of all descendants.
+    pub aggregated_time_secs: u64,
+    pub formatted_aggregated_time: String,
+    pub priority: Priority,
 }
 
 impl From<&Task> for TaskDto {
     fn from(task: &Task) -> Self {
         let current_time = task.get_current_time();
+        let today = Local::now().date_naive();
+        let week_start = today - chrono::Duration::days(today.weekday().num_days_from_monday() as i64);
+
         Self {
             name: task.name.clone(),
             total_time_secs: current_time.as_secs(),
             formatted_time: Task::format_duration(current_time),
             is_running: task.is_running,
             created_at: task.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            entries: task.entries.clone(),
+            today_secs: task.time_in_range(today, today).as_secs(),
+            week_secs: task.time_in_range(week_start, today).as_secs(),
+            tags: task.tags.clone(),
+            parent: task.parent.clone(),
+            aggregated_time_secs: current_time.as_secs(),
+            formatted_aggregated_time: Task::format_duration(current_time),
+            priority: task.priority,
+        }
+    }
+}
+
+impl TaskDto {
+    /// Builds DTOs for every task, with `aggregated_time_secs` summing each task's own
+    /// current time plus the recursively accumulated current time of its descendants.
+    pub fn build_list(tasks: &std::collections::HashMap<String, Task>) -> Vec<TaskDto> {
+        tasks
+            .values()
+            .map(|task| Self::with_aggregate(task, tasks))
+            .collect()
+    }
+
+    /// Builds a single DTO with its aggregated descendant time filled in.
+    pub fn with_aggregate(task: &Task, tasks: &std::collections::HashMap<String, Task>) -> Self {
+        let mut dto = Self::from(task);
+        let aggregated = Self::aggregate_time(&task.name, tasks);
+        dto.aggregated_time_secs = aggregated.as_secs();
+        dto.formatted_aggregated_time = Task::format_duration(aggregated);
+        dto
+    }
+
+    fn aggregate_time(name: &str, tasks: &std::collections::HashMap<String, Task>) -> Duration {
+        let mut total = tasks
+            .get(name)
+            .map(Task::get_current_time)
+            .unwrap_or_default();
+
+        for (child_name, child) in tasks {
+            if child.parent.as_deref() == Some(name) {
+                total += Self::aggregate_time(child_name, tasks);
+            }
         }
+
+        total
     }
 }
 
@@ -139,4 +314,29 @@ mod tests {
         assert_eq!(Task::format_duration(Duration::from_secs(59)), "00:00:59");
         assert_eq!(Task::format_duration(Duration::from_secs(3661)), "01:01:01");
     }
+
+    #[test]
+    fn test_log_session_splits_at_midnight() {
+        let mut entries = Vec::new();
+        let start = Local.with_ymd_and_hms(2024, 1, 1, 23, 0, 0).unwrap();
+        let end = Local.with_ymd_and_hms(2024, 1, 2, 1, 0, 0).unwrap();
+
+        Task::log_session(&mut entries, start, end);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].duration, Duration::from_secs(3600));
+        assert_eq!(entries[1].duration, Duration::from_secs(3600));
+    }
+
+    #[test]
+    fn test_log_session_merges_same_day() {
+        let mut entries = Vec::new();
+        let day = Local.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap();
+
+        Task::log_session(&mut entries, day, day + chrono::Duration::minutes(30));
+        Task::log_session(&mut entries, day + chrono::Duration::hours(1), day + chrono::Duration::hours(2));
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].duration, Duration::from_secs(90 * 60));
+    }
 }
\ No newline at end of file
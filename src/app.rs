@@ -1,8 +1,13 @@
 use eframe::egui::{self, Vec2};
+use egui_plot::{Bar, BarChart, Plot};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
-use crate::task::Task;
+use crate::task::{Priority, Task};
 use crate::persistence::Persistence;
+use crate::pomodoro::{PomodoroPhase, PomodoroSettings};
+use crate::report;
 use crate::ui::MaterialUI;
 
 #[derive(Default)]
@@ -15,6 +20,17 @@ pub struct TimeTrackerApp {
     pub task_to_delete: String,
     pub dark_mode: bool,
     pub last_save_time: Option<Instant>,
+    pub pomodoro_settings: PomodoroSettings,
+    pub show_settings_dialog: bool,
+    pub tag_filter: Option<String>,
+    pub sort_by_priority: bool,
+    pub show_report: bool,
+    pub report_task_filter: Option<String>,
+    /// Set by the file watcher (see `Persistence::watch_for_changes`) whenever the save file
+    /// changes on disk, including through this app's own saves; polled and cleared on a
+    /// debounced interval in `update()`.
+    pub file_changed: Arc<AtomicBool>,
+    pub last_watch_check: Option<Instant>,
 }
 
 impl TimeTrackerApp {
@@ -25,6 +41,8 @@ impl TimeTrackerApp {
         // Load existing tasks from file
         let mut app = Self::default();
         app.tasks = Persistence::load_tasks();
+        app.pomodoro_settings = Persistence::load_settings();
+        app.file_changed = Persistence::watch_for_changes();
         app
     }
 
@@ -32,67 +50,200 @@ impl TimeTrackerApp {
         Persistence::save_tasks(&self.tasks);
     }
 
-    fn export_to_txt(&self) {
-        Persistence::export_to_txt(&self.tasks);
+    fn export_tasks(&self) {
+        Persistence::export_tasks(&self.tasks);
+    }
+
+    /// Opens a file picker for another device's save file and merges it into the live state.
+    fn import_tasks(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("JSON files", &["json"])
+            .pick_file()
+        else {
+            return;
+        };
+
+        match std::fs::read_to_string(&path) {
+            Ok(json_data) => match serde_json::from_str::<HashMap<String, Task>>(&json_data) {
+                Ok(incoming) => {
+                    self.merge_tasks(incoming);
+                    self.save_tasks();
+                }
+                Err(e) => eprintln!("Failed to parse '{}': {}", path.display(), e),
+            },
+            Err(e) => eprintln!("Failed to read '{}': {}", path.display(), e),
+        }
+    }
+
+    /// Merges an imported task set into `self.tasks`: tasks present only on one side are
+    /// kept as-is, tasks present on both are merged field-by-field with "last modified
+    /// wins", and per-day entries are unioned by summing the two sides' durations for a
+    /// shared day. This crate has no per-session record to de-duplicate against (unlike
+    /// the Tauri build's `sessions`), so re-importing the same file twice double-counts;
+    /// that's the right tradeoff here, since the feature's actual use case is combining
+    /// genuinely distinct time tracked on two different machines for the same day, and
+    /// discarding one side's time (as taking the larger duration did) silently loses it.
+    fn merge_tasks(&mut self, incoming: HashMap<String, Task>) {
+        for (name, other) in incoming {
+            match self.tasks.get_mut(&name) {
+                None => {
+                    self.tasks.insert(name, other);
+                }
+                Some(existing) => Self::merge_task(existing, &other),
+            }
+        }
+    }
+
+    fn merge_task(existing: &mut Task, other: &Task) {
+        for entry in &other.entries {
+            match existing.entries.iter_mut().find(|e| e.logged_date == entry.logged_date) {
+                Some(e) => e.duration += entry.duration,
+                None => existing.entries.push(entry.clone()),
+            }
+        }
+        existing.total_time = existing.entries.iter().fold(Duration::ZERO, |acc, e| acc + e.duration);
+
+        if other.modified_at > existing.modified_at {
+            existing.tags = other.tags.clone();
+            existing.parent = other.parent.clone();
+            existing.priority = other.priority;
+            existing.modified_at = other.modified_at;
+        }
     }
 
     fn toggle_theme(&mut self, ctx: &egui::Context) {
         MaterialUI::toggle_theme(ctx, &mut self.dark_mode);
     }
 
-    fn render_task_card(&mut self, ui: &mut egui::Ui, task_name: &str, task: &Task) {
+    fn render_task_card(&mut self, ui: &mut egui::Ui, task_name: &str, task: &Task, depth: usize) {
         let task_name_owned = task_name.to_string();
-        let (delete_clicked, reset_clicked, start_stop_clicked) = MaterialUI::material_card(ui, true, |ui| {
-            ui.horizontal(|ui| {
-                // Task name
-                ui.label(egui::RichText::new(task_name).size(16.0).strong());
-                
-                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    // Delete button
-                    let delete_clicked = MaterialUI::material_button(ui, "🗑", false).clicked();
-                    
-                    ui.add_space(8.0);
-                    
-                    // Reset button
-                    let reset_clicked = MaterialUI::material_button(ui, "Reset", false).clicked();
-                    
-                    ui.add_space(8.0);
-                    
-                    // Start/Stop button
-                    let button_text = if task.is_running { "Stop" } else { "Start" };
-                    let start_stop_clicked = MaterialUI::material_button(ui, button_text, task.is_running).clicked();
-                    
-                    ui.add_space(8.0);
-                    
-                    // Running indicator
-                    if task.is_running {
-                        ui.spinner();
+        let has_children = self.tasks.values().any(|t| t.parent.as_deref() == Some(task_name));
+        let aggregated = self.aggregated_time(task_name);
+        let (delete_clicked, reset_clicked, start_stop_clicked, pomodoro_toggle_clicked, priority_clicked) =
+            MaterialUI::material_card(ui, true, |ui| {
+                ui.horizontal(|ui| {
+                    // Indent nested tasks under their parent
+                    ui.add_space(depth as f32 * 20.0);
+                    let prefix = if depth > 0 { "↳ " } else { "" };
+                    ui.label(egui::RichText::new(format!("{}{}", prefix, task_name)).size(16.0).strong());
+
+                    ui.add_space(6.0);
+                    let priority_color = match task.priority {
+                        Priority::Low => egui::Color32::from_rgb(76, 175, 80),
+                        Priority::Medium => egui::Color32::from_rgb(255, 160, 0),
+                        Priority::High => egui::Color32::from_rgb(211, 47, 47),
+                    };
+                    // Clicking the dot cycles Low -> Medium -> High -> Low.
+                    let priority_clicked = ui
+                        .add(egui::Label::new(egui::RichText::new("●").color(priority_color)).sense(egui::Sense::click()))
+                        .on_hover_text("Click to change priority")
+                        .clicked();
+
+                    if has_children {
                         ui.add_space(8.0);
+                        ui.label(
+                            egui::RichText::new(format!("(total: {})", Task::format_duration(aggregated)))
+                                .size(12.0)
+                                .weak(),
+                        );
                     }
-                    
-                    // Time display
-                    let time_str = Task::format_duration(task.get_current_time());
-                    ui.label(egui::RichText::new(&time_str).size(18.0).monospace());
-                    
-                    (delete_clicked, reset_clicked, start_stop_clicked)
+
+                    if task.pomodoro_enabled {
+                        ui.add_space(8.0);
+                        let (label, color) = match task.pomodoro_phase {
+                            Some(PomodoroPhase::Work) => ("Work", egui::Color32::from_rgb(103, 80, 164)),
+                            Some(PomodoroPhase::ShortBreak) => ("Break", egui::Color32::from_rgb(56, 142, 60)),
+                            Some(PomodoroPhase::LongBreak) => ("Long Break", egui::Color32::from_rgb(33, 150, 243)),
+                            None => ("Paused", egui::Color32::GRAY),
+                        };
+                        ui.label(
+                            egui::RichText::new(format!("{} · {} cycles", label, task.completed_pomodoro_cycles))
+                                .size(12.0)
+                                .color(color)
+                                .strong(),
+                        );
+                    }
+
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        // Delete button
+                        let delete_clicked = MaterialUI::material_button(ui, "🗑", false).clicked();
+
+                        ui.add_space(8.0);
+
+                        // Reset button
+                        let reset_clicked = MaterialUI::material_button(ui, "Reset", false).clicked();
+
+                        ui.add_space(8.0);
+
+                        // Pomodoro toggle
+                        let pomodoro_toggle_clicked =
+                            MaterialUI::material_button(ui, "🍅", task.pomodoro_enabled).clicked();
+
+                        ui.add_space(8.0);
+
+                        // Start/Stop button
+                        let button_text = if task.is_running { "Stop" } else { "Start" };
+                        let start_stop_clicked = MaterialUI::material_button(ui, button_text, task.is_running).clicked();
+
+                        ui.add_space(8.0);
+
+                        // Running indicator
+                        if task.is_running {
+                            ui.spinner();
+                            ui.add_space(8.0);
+                        }
+
+                        // Time display
+                        let time_str = Task::format_duration(task.get_current_time());
+                        ui.label(egui::RichText::new(&time_str).size(18.0).monospace());
+
+                        (delete_clicked, reset_clicked, start_stop_clicked, pomodoro_toggle_clicked, priority_clicked)
+                    }).inner
                 }).inner
-            }).inner
-        });
-        
+            });
+
         if delete_clicked {
             self.show_delete_dialog = true;
             self.task_to_delete = task_name_owned.clone();
         }
-        
+
         if reset_clicked {
             self.show_reset_dialog = true;
-            self.task_to_reset = task_name_owned;
+            self.task_to_reset = task_name_owned.clone();
         }
-        
+
+        if pomodoro_toggle_clicked {
+            if let Some(task) = self.tasks.get_mut(task_name) {
+                if task.pomodoro_enabled {
+                    task.stop_pomodoro();
+                } else {
+                    task.start_pomodoro();
+                }
+                self.save_tasks();
+            }
+        }
+
+        if priority_clicked {
+            if let Some(task) = self.tasks.get_mut(task_name) {
+                task.priority = match task.priority {
+                    Priority::Low => Priority::Medium,
+                    Priority::Medium => Priority::High,
+                    Priority::High => Priority::Low,
+                };
+                self.save_tasks();
+            }
+        }
+
         if start_stop_clicked {
             if let Some(task) = self.tasks.get_mut(task_name) {
                 if task.is_running {
-                    task.stop();
+                    if task.pomodoro_enabled {
+                        task.stop_pomodoro();
+                    } else {
+                        task.stop();
+                    }
+                } else if task.pomodoro_enabled {
+                    task.start_pomodoro();
                 } else {
                     task.start();
                 }
@@ -101,6 +252,198 @@ impl TimeTrackerApp {
             }
         }
     }
+
+    /// Own current time plus the recursively accumulated current time of all descendants.
+    fn aggregated_time(&self, name: &str) -> Duration {
+        let mut total = self.tasks.get(name).map(Task::get_current_time).unwrap_or_default();
+        for (child_name, child) in &self.tasks {
+            if child.parent.as_deref() == Some(name) {
+                total += self.aggregated_time(child_name);
+            }
+        }
+        total
+    }
+
+    fn collect_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self.tasks.values().flat_map(|t| t.tags.clone()).collect();
+        tags.sort();
+        tags.dedup();
+        tags
+    }
+
+    /// When a tag filter is active, returns the set of tasks matching it plus their ancestors
+    /// (so the tree stays connected), otherwise `None` to mean "show everything".
+    fn visible_for_tag_filter(&self) -> Option<std::collections::HashSet<String>> {
+        let tag = self.tag_filter.as_ref()?;
+        let matching: Vec<String> = self
+            .tasks
+            .iter()
+            .filter(|(_, t)| t.tags.iter().any(|x| x == tag))
+            .map(|(name, _)| name.clone())
+            .collect();
+
+        let mut visible = std::collections::HashSet::new();
+        for name in matching {
+            let mut current = Some(name);
+            while let Some(n) = current {
+                if !visible.insert(n.clone()) {
+                    break;
+                }
+                current = self.tasks.get(&n).and_then(|t| t.parent.clone());
+            }
+        }
+        Some(visible)
+    }
+
+    /// Renders the subtree rooted at `parent` (`None` for top-level tasks), recursing into
+    /// children sorted by `created_at`, or by priority (highest first) then `created_at`
+    /// when `sort_by_priority` is set.
+    fn render_task_tree(
+        &mut self,
+        ui: &mut egui::Ui,
+        parent: Option<&str>,
+        depth: usize,
+        visible: &Option<std::collections::HashSet<String>>,
+    ) {
+        let mut children: Vec<(String, Task)> = self
+            .tasks
+            .iter()
+            .filter(|(name, t)| {
+                t.parent.as_deref() == parent && visible.as_ref().map_or(true, |v| v.contains(*name))
+            })
+            .map(|(name, t)| (name.clone(), t.clone()))
+            .collect();
+        if self.sort_by_priority {
+            children.sort_by(|a, b| {
+                b.1.priority.cmp(&a.1.priority).then(a.1.created_at.cmp(&b.1.created_at))
+            });
+        } else {
+            children.sort_by(|a, b| a.1.created_at.cmp(&b.1.created_at));
+        }
+
+        for (name, task) in children {
+            self.render_task_card(ui, &name, &task, depth);
+            ui.add_space(8.0);
+            self.render_task_tree(ui, Some(&name), depth + 1, visible);
+        }
+    }
+
+    /// Renders the time-report panel: a task filter, a compact textual summary, and a
+    /// bar chart of hours tracked per day over the last two weeks.
+    fn render_report_panel(&mut self, ui: &mut egui::Ui) {
+        let totals = report::daily_totals(&self.tasks, self.report_task_filter.as_deref(), 14);
+        let week = report::week_total(&totals);
+        let average = report::daily_average(&totals);
+        let most_tracked = report::most_tracked_task(&self.tasks).unwrap_or_else(|| "-".to_string());
+
+        MaterialUI::material_card(ui, true, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Time Report").size(16.0).strong());
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let mut task_names: Vec<String> = self.tasks.keys().cloned().collect();
+                    task_names.sort();
+
+                    egui::ComboBox::from_id_source("report_task_filter")
+                        .selected_text(self.report_task_filter.clone().unwrap_or_else(|| "All tasks".to_string()))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.report_task_filter, None, "All tasks");
+                            for name in task_names {
+                                ui.selectable_value(&mut self.report_task_filter, Some(name.clone()), name);
+                            }
+                        });
+                });
+            });
+
+            ui.add_space(4.0);
+            ui.label(format!(
+                "This week: {}   ·   Daily average: {}   ·   Most tracked: {}",
+                Task::format_duration(week),
+                Task::format_duration(average),
+                most_tracked,
+            ));
+            ui.add_space(8.0);
+
+            let bars: Vec<Bar> = totals
+                .iter()
+                .enumerate()
+                .map(|(i, total)| Bar::new(i as f64, total.duration.as_secs_f64() / 3600.0))
+                .collect();
+
+            Plot::new("daily_hours_plot")
+                .height(180.0)
+                .allow_scroll(false)
+                .show(ui, |plot_ui| {
+                    plot_ui.bar_chart(BarChart::new(bars).name("Hours"));
+                });
+        });
+    }
+
+    /// Checks (on a debounced ~500ms interval) whether the save file was changed
+    /// externally, and if so, reloads it into memory.
+    fn poll_external_changes(&mut self) {
+        let now = Instant::now();
+        let due = self
+            .last_watch_check
+            .map_or(true, |last| now.duration_since(last) >= Duration::from_millis(500));
+        if !due {
+            return;
+        }
+        self.last_watch_check = Some(now);
+
+        if self.file_changed.swap(false, Ordering::SeqCst) {
+            self.reload_external_changes();
+        }
+    }
+
+    /// Merges externally reloaded tasks into memory: a locally-running task, or one in a
+    /// Pomodoro break, keeps its in-memory session untouched, while other stopped tasks
+    /// adopt the on-disk copy.
+    fn reload_external_changes(&mut self) {
+        for (name, external_task) in Persistence::load_tasks() {
+            let keep_local = self
+                .tasks
+                .get(&name)
+                .is_some_and(|t| t.is_running || t.pomodoro_phase.is_some());
+            if !keep_local {
+                self.tasks.insert(name, external_task);
+            }
+        }
+        println!("Reloaded tasks after external change to the save file");
+    }
+
+    /// Advances any running Pomodoro intervals, firing a desktop notification on transition.
+    fn tick_pomodoros(&mut self) {
+        let mut transitions: Vec<(String, PomodoroPhase)> = Vec::new();
+        for (name, task) in self.tasks.iter_mut() {
+            if task.pomodoro_enabled {
+                if let Some(new_phase) = task.tick_pomodoro(&self.pomodoro_settings) {
+                    transitions.push((name.clone(), new_phase));
+                }
+            }
+        }
+        if !transitions.is_empty() {
+            self.save_tasks();
+        }
+        for (name, phase) in transitions {
+            Self::notify_phase_change(&name, phase);
+        }
+    }
+
+    fn notify_phase_change(task_name: &str, phase: PomodoroPhase) {
+        let message = match phase {
+            PomodoroPhase::Work => format!("\"{}\": back to work", task_name),
+            PomodoroPhase::ShortBreak => format!("\"{}\": short break", task_name),
+            PomodoroPhase::LongBreak => format!("\"{}\": long break", task_name),
+        };
+        if let Err(e) = notify_rust::Notification::new()
+            .summary("Time Tracker")
+            .body(&message)
+            .show()
+        {
+            eprintln!("Failed to show notification: {}", e);
+        }
+    }
 }
 
 impl eframe::App for TimeTrackerApp {
@@ -112,7 +455,10 @@ impl eframe::App for TimeTrackerApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // Request repaint for smooth time updates
         ctx.request_repaint_after(Duration::from_millis(100));
-        
+
+        self.tick_pomodoros();
+        self.poll_external_changes();
+
         // Periodic save every 30 seconds to capture running task times
         let now = Instant::now();
         let should_save = match self.last_save_time {
@@ -133,11 +479,54 @@ impl eframe::App for TimeTrackerApp {
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     // Export button
                     if MaterialUI::material_button(ui, "Export", false).clicked() {
-                        self.export_to_txt();
+                        self.export_tasks();
                     }
-                    
+
                     ui.add_space(8.0);
-                    
+
+                    // Import button: merges tasks saved on another device into this one
+                    if MaterialUI::material_button(ui, "Import", false).clicked() {
+                        self.import_tasks();
+                    }
+
+                    ui.add_space(8.0);
+
+                    // Report panel toggle
+                    if MaterialUI::material_button(ui, "📊", self.show_report).clicked() {
+                        self.show_report = !self.show_report;
+                    }
+
+                    ui.add_space(8.0);
+
+                    // Tag filter: collapses the tree to matching tasks and their ancestors
+                    let tags = self.collect_tags();
+                    if !tags.is_empty() {
+                        egui::ComboBox::from_id_source("tag_filter")
+                            .selected_text(self.tag_filter.clone().unwrap_or_else(|| "All tags".to_string()))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut self.tag_filter, None, "All tags");
+                                for tag in tags {
+                                    ui.selectable_value(&mut self.tag_filter, Some(tag.clone()), tag);
+                                }
+                            });
+                        ui.add_space(8.0);
+                    }
+
+                    // Sort toggle: priority (then created_at) vs. created_at only
+                    let sort_label = if self.sort_by_priority { "Sort: Priority" } else { "Sort: Date" };
+                    if MaterialUI::material_button(ui, sort_label, self.sort_by_priority).clicked() {
+                        self.sort_by_priority = !self.sort_by_priority;
+                    }
+
+                    ui.add_space(8.0);
+
+                    // Pomodoro settings toggle
+                    if MaterialUI::material_button(ui, "⚙ Pomodoro", self.show_settings_dialog).clicked() {
+                        self.show_settings_dialog = !self.show_settings_dialog;
+                    }
+
+                    ui.add_space(8.0);
+
                     // Theme toggle button
                     let theme_icon = if self.dark_mode { "🌙" } else { "☀️" };
                     if ui.button(theme_icon).clicked() {
@@ -150,7 +539,12 @@ impl eframe::App for TimeTrackerApp {
         // Main content area
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.spacing_mut().item_spacing.y = 12.0;
-            
+
+            if self.show_report {
+                self.render_report_panel(ui);
+                ui.separator();
+            }
+
             // Add task section
             let add_clicked = MaterialUI::material_card(ui, false, |ui| {
                 ui.horizontal(|ui| {
@@ -189,17 +583,9 @@ impl eframe::App for TimeTrackerApp {
                     ui.label(egui::RichText::new("No tasks yet. Add a task to get started!").size(16.0));
                 });
             } else {
-                // Collect task data first to avoid borrowing issues
-                let mut tasks_to_render: Vec<(String, Task)> = self.tasks.iter()
-                    .map(|(name, task)| (name.clone(), task.clone()))
-                    .collect();
-                tasks_to_render.sort_by(|a, b| a.1.created_at.cmp(&b.1.created_at));
-                
+                let visible = self.visible_for_tag_filter();
                 egui::ScrollArea::vertical().show(ui, |ui| {
-                    for (task_name, task) in &tasks_to_render {
-                        self.render_task_card(ui, task_name, task);
-                        ui.add_space(8.0);
-                    }
+                    self.render_task_tree(ui, None, 0, &visible);
                 });
             }
         });
@@ -261,5 +647,45 @@ impl eframe::App for TimeTrackerApp {
                     });
                 });
         }
+
+        // Pomodoro settings dialog
+        if self.show_settings_dialog {
+            egui::Window::new("Pomodoro Settings")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, Vec2::ZERO)
+                .show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Work (minutes):");
+                        ui.add(egui::DragValue::new(&mut self.pomodoro_settings.work_minutes).clamp_range(1..=180));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Short break (minutes):");
+                        ui.add(egui::DragValue::new(&mut self.pomodoro_settings.break_minutes).clamp_range(1..=60));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Long break (minutes):");
+                        ui.add(egui::DragValue::new(&mut self.pomodoro_settings.long_break_minutes).clamp_range(1..=120));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Cycles before long break:");
+                        ui.add(egui::DragValue::new(&mut self.pomodoro_settings.cycles_before_long_break).clamp_range(1..=12));
+                    });
+                    ui.add_space(10.0);
+
+                    ui.horizontal(|ui| {
+                        if MaterialUI::material_button(ui, "Close", false).clicked() {
+                            self.show_settings_dialog = false;
+                        }
+
+                        ui.add_space(8.0);
+
+                        if MaterialUI::material_button(ui, "Save", true).clicked() {
+                            Persistence::save_settings(&self.pomodoro_settings);
+                            self.show_settings_dialog = false;
+                        }
+                    });
+                });
+        }
     }
 }
\ No newline at end of file
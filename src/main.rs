@@ -1,5 +1,7 @@
 mod task;
 mod persistence;
+mod pomodoro;
+mod report;
 mod ui;
 mod app;
 
@@ -1,8 +1,16 @@
 use std::collections::HashMap;
 use std::fs;
+use std::io::Write;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use crate::pomodoro::PomodoroSettings;
 use crate::task::Task;
 
+/// Number of rotated backups of the save file to retain; the oldest is pruned once
+/// a save would exceed this.
+const MAX_BACKUPS: usize = 5;
+
 pub struct Persistence;
 
 impl Persistence {
@@ -10,13 +18,13 @@ impl Persistence {
         // Get the user's config directory or fallback to current directory
         let config_dir = dirs::config_dir()
             .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
-        
+
         config_dir.join("time_tracker_data.json")
     }
 
     pub fn save_tasks(tasks: &HashMap<String, Task>) {
         let save_path = Self::get_save_path();
-        
+
         // Create parent directory if it doesn't exist
         if let Some(parent) = save_path.parent() {
             if let Err(e) = fs::create_dir_all(parent) {
@@ -25,9 +33,9 @@ impl Persistence {
             }
         }
 
-        match serde_json::to_string_pretty(tasks) {
+        match Self::to_canonical_json(tasks) {
             Ok(json_data) => {
-                if let Err(e) = fs::write(&save_path, json_data) {
+                if let Err(e) = Self::write_atomically(&save_path, &json_data) {
                     eprintln!("Failed to save tasks: {}", e);
                 } else {
                     println!("Tasks saved to: {}", save_path.display());
@@ -39,69 +47,410 @@ impl Persistence {
         }
     }
 
+    /// Serializes tasks with sorted map keys, so repeated saves of unchanged state
+    /// produce byte-identical output regardless of `HashMap` iteration order. This keeps
+    /// the save file diffable in git and comparable across machines for a future sync feature.
+    fn to_canonical_json(tasks: &HashMap<String, Task>) -> serde_json::Result<String> {
+        let canonical: std::collections::BTreeMap<&String, &Task> = tasks.iter().collect();
+        serde_json::to_string_pretty(&canonical)
+    }
+
+    /// Writes `data` to `path` crash-safely. The new contents are written to a temporary
+    /// file in the same directory and fsynced, the existing good file (if any) is rotated
+    /// into a timestamped backup, and only then is the temp file renamed over `path`.
+    /// `fs::rename` is atomic within a filesystem, so a crash mid-write can never leave
+    /// `path` truncated or partially written.
+    fn write_atomically(path: &std::path::Path, data: &str) -> std::io::Result<()> {
+        let tmp_path = path.with_extension("json.tmp");
+        {
+            let mut tmp_file = fs::File::create(&tmp_path)?;
+            tmp_file.write_all(data.as_bytes())?;
+            tmp_file.sync_all()?;
+        }
+
+        if path.exists() {
+            Self::rotate_backup(path)?;
+        }
+
+        fs::rename(&tmp_path, path)?;
+
+        // A failure here must not fail the save: the rename already committed good data
+        // to `path`. If the sidecar can't be (re)written, remove it instead of leaving it
+        // describing the old contents, so `verify_integrity` falls back to its "no sidecar"
+        // trusted path rather than flagging freshly-saved data as corrupted.
+        if let Err(e) = Self::write_hash_sidecar(path, data) {
+            eprintln!("Warning: failed to write integrity sidecar for {}: {}", path.display(), e);
+            let _ = fs::remove_file(Self::hash_sidecar_path(path));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the path of the BLAKE3 digest sidecar for a save file.
+    fn hash_sidecar_path(path: &std::path::Path) -> PathBuf {
+        path.with_extension("json.blake3")
+    }
+
+    /// Writes the hex-encoded BLAKE3 digest of `data` to `path`'s sidecar file, so
+    /// `load_tasks` can detect a save file that was corrupted or partially rewritten
+    /// after the fact.
+    fn write_hash_sidecar(path: &std::path::Path, data: &str) -> std::io::Result<()> {
+        let digest = blake3::hash(data.as_bytes()).to_hex();
+        fs::write(Self::hash_sidecar_path(path), digest.as_str())
+    }
+
+    /// Verifies `data` against the BLAKE3 digest recorded in its sidecar, if one exists.
+    /// A missing sidecar (e.g. a file saved before this feature existed) is treated as
+    /// trusted rather than a failure.
+    fn verify_integrity(path: &std::path::Path, data: &str) -> Result<(), String> {
+        let sidecar = Self::hash_sidecar_path(path);
+        if !sidecar.exists() {
+            return Ok(());
+        }
+
+        let expected = fs::read_to_string(&sidecar).map_err(|e| e.to_string())?;
+        let actual = blake3::hash(data.as_bytes()).to_hex();
+        if expected.trim() != actual.as_str() {
+            return Err("data file integrity check failed: BLAKE3 digest mismatch".to_string());
+        }
+        Ok(())
+    }
+
+    /// Copies the current good file to a timestamped backup alongside it, then prunes
+    /// backups beyond `MAX_BACKUPS`, oldest first.
+    ///
+    /// Several saves can land within the same wall-clock second (a few quick clicks each
+    /// trigger their own `save()`), so the timestamp alone isn't a unique suffix: if it
+    /// collided, `fs::copy` would overwrite the previous backup with the file it was meant
+    /// to protect. Bump the suffix by one past any existing backup with the same timestamp
+    /// to keep every rotation distinct.
+    fn rotate_backup(path: &std::path::Path) -> std::io::Result<()> {
+        let parent = path.parent().unwrap_or_else(|| std::path::Path::new("."));
+        let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("data.json");
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let prefix = format!("{}.bak.{}", file_name, timestamp);
+        let mut suffix = 0u32;
+        let mut backup_path = parent.join(&prefix);
+        while backup_path.exists() {
+            suffix += 1;
+            backup_path = parent.join(format!("{}.{}", prefix, suffix));
+        }
+
+        fs::copy(path, &backup_path)?;
+        Self::prune_backups(parent, file_name)
+    }
+
+    /// Removes the oldest backups of `file_name` in `parent` until at most `MAX_BACKUPS` remain.
+    fn prune_backups(parent: &std::path::Path, file_name: &str) -> std::io::Result<()> {
+        let prefix = format!("{}.bak.", file_name);
+        let mut backups: Vec<_> = fs::read_dir(parent)?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| {
+                p.file_name()
+                    .and_then(|n| n.to_str())
+                    .map(|n| n.starts_with(&prefix))
+                    .unwrap_or(false)
+            })
+            .collect();
+        backups.sort();
+        while backups.len() > MAX_BACKUPS {
+            let oldest = backups.remove(0);
+            let _ = fs::remove_file(oldest);
+        }
+        Ok(())
+    }
+
+    /// Loads tasks from the persistent storage file, falling back to the latest backup
+    /// if the main file fails its integrity check or fails to deserialize. A missing main
+    /// file yields an empty map; backups are not consulted in that case.
     pub fn load_tasks() -> HashMap<String, Task> {
         let save_path = Self::get_save_path();
-        
+
         if !save_path.exists() {
             println!("No saved tasks found, starting fresh");
             return HashMap::new();
         }
 
-        match fs::read_to_string(&save_path) {
-            Ok(json_data) => {
-                match serde_json::from_str::<HashMap<String, Task>>(&json_data) {
-                    Ok(loaded_tasks) => {
-                        println!("Loaded {} tasks from: {}", loaded_tasks.len(), save_path.display());
+        match Self::load_from(&save_path) {
+            Ok(loaded_tasks) => {
+                println!("Loaded {} tasks from: {}", loaded_tasks.len(), save_path.display());
+                loaded_tasks
+            }
+            Err(e) => {
+                eprintln!("Failed to load tasks from {}: {}", save_path.display(), e);
+                match Self::latest_backup(&save_path).and_then(|p| Self::load_from(&p).ok()) {
+                    Some(loaded_tasks) => {
+                        eprintln!("Recovered {} tasks from latest backup", loaded_tasks.len());
                         loaded_tasks
                     }
-                    Err(e) => {
-                        eprintln!("Failed to deserialize tasks: {}", e);
+                    None => {
+                        eprintln!("No usable backup found, starting fresh");
                         HashMap::new()
                     }
                 }
             }
-            Err(e) => {
-                eprintln!("Failed to read tasks file: {}", e);
-                HashMap::new()
+        }
+    }
+
+    /// Reads and deserializes tasks from a specific file, rejecting it if its contents
+    /// don't match the recorded BLAKE3 digest.
+    fn load_from(path: &std::path::Path) -> Result<HashMap<String, Task>, String> {
+        let json_data = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        Self::verify_integrity(path, &json_data)?;
+        serde_json::from_str(&json_data).map_err(|e| e.to_string())
+    }
+
+    /// Returns the most recent backup of `path`, if any exist.
+    fn latest_backup(path: &std::path::Path) -> Option<PathBuf> {
+        let parent = path.parent()?;
+        let file_name = path.file_name()?.to_str()?;
+        let prefix = format!("{}.bak.", file_name);
+        fs::read_dir(parent)
+            .ok()?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| {
+                p.file_name()
+                    .and_then(|n| n.to_str())
+                    .map(|n| n.starts_with(&prefix))
+                    .unwrap_or(false)
+            })
+            .max()
+    }
+
+    /// Watches the save file for external changes (e.g. hand edits or a sync tool) and
+    /// returns a flag that is set whenever the file is modified on disk. The caller is
+    /// expected to poll and reset the flag on a debounced interval (~500ms), since
+    /// filesystem events often arrive in bursts for a single logical write.
+    ///
+    /// Watches the *parent directory* rather than the save file itself: on Linux, inotify
+    /// watches are keyed by inode, and `write_atomically`'s own write-then-rename (like any
+    /// atomic replace) deletes the original inode, which silently stops a watch placed on
+    /// the file path after the very first external write. Watching the directory survives
+    /// that, so events are filtered down to the save file's name instead.
+    pub fn watch_for_changes() -> Arc<AtomicBool> {
+        let changed = Arc::new(AtomicBool::new(false));
+        let flag = changed.clone();
+        let save_path = Self::get_save_path();
+        let file_name = save_path.file_name().map(|n| n.to_os_string());
+
+        let watch_result = (|| -> notify::Result<()> {
+            let parent = save_path
+                .parent()
+                .map(|p| p.to_path_buf())
+                .unwrap_or_else(|| std::path::PathBuf::from("."));
+
+            let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+                if let Ok(event) = res {
+                    let matches_save_file = event
+                        .paths
+                        .iter()
+                        .any(|p| p.file_name() == file_name.as_deref());
+                    if matches_save_file {
+                        flag.store(true, Ordering::SeqCst);
+                    }
+                }
+            })?;
+            watcher.watch(&parent, notify::RecursiveMode::NonRecursive)?;
+            // Leak the watcher so it keeps running for the life of the process;
+            // there's no app shutdown hook to drop it from cleanly.
+            std::mem::forget(watcher);
+            Ok(())
+        })();
+
+        if let Err(e) = watch_result {
+            eprintln!("Failed to watch save file for changes: {}", e);
+        }
+
+        changed
+    }
+
+    /// Path of the Pomodoro settings file, kept separate from the task save file since
+    /// the two are edited and saved on entirely different triggers.
+    fn get_settings_path() -> PathBuf {
+        let config_dir = dirs::config_dir()
+            .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
+
+        config_dir.join("time_tracker_settings.json")
+    }
+
+    /// Persists the user's Pomodoro interval lengths. Unlike `save_tasks`, this isn't on
+    /// the crash-safety path (losing a settings edit is much lower stakes than losing
+    /// tracked time), so a plain write is enough.
+    pub fn save_settings(settings: &PomodoroSettings) {
+        let path = Self::get_settings_path();
+
+        if let Some(parent) = path.parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                eprintln!("Failed to create config directory: {}", e);
+                return;
             }
         }
+
+        match serde_json::to_string_pretty(settings) {
+            Ok(json_data) => {
+                if let Err(e) = fs::write(&path, json_data) {
+                    eprintln!("Failed to save Pomodoro settings: {}", e);
+                }
+            }
+            Err(e) => eprintln!("Failed to serialize Pomodoro settings: {}", e),
+        }
+    }
+
+    /// Loads the user's Pomodoro interval lengths, falling back to the defaults if no
+    /// settings file exists yet or it fails to parse.
+    pub fn load_settings() -> PomodoroSettings {
+        let path = Self::get_settings_path();
+
+        match fs::read_to_string(&path) {
+            Ok(json_data) => serde_json::from_str(&json_data).unwrap_or_default(),
+            Err(_) => PomodoroSettings::default(),
+        }
     }
 
-    pub fn export_to_txt(tasks: &HashMap<String, Task>) {
-        use chrono::Local;
+    /// Opens a save dialog offering every registered export format and writes the chosen
+    /// file using the `Exporter` matching the selected extension.
+    pub fn export_tasks(tasks: &HashMap<String, Task>) {
         use std::fs::File;
-        use std::io::Write;
-        
+
         if let Some(path) = rfd::FileDialog::new()
             .add_filter("Text files", &["txt"])
+            .add_filter("CSV files", &["csv"])
+            .add_filter("JSON files", &["json"])
+            .add_filter("Markdown files", &["md"])
             .set_file_name("time_tracker_export.txt")
             .save_file()
         {
+            let exporter = exporter_for_extension(
+                path.extension().and_then(|e| e.to_str()).unwrap_or("txt"),
+            );
+
             match File::create(&path) {
-                Ok(mut file) => {
-                    writeln!(file, "Time Tracker Export").unwrap();
-                    writeln!(file, "Generated on: {}", Local::now().format("%Y-%m-%d %H:%M:%S")).unwrap();
-                    writeln!(file, "").unwrap();
-                    
-                    let mut sorted_tasks: Vec<_> = tasks.iter().collect();
-                    sorted_tasks.sort_by(|a, b| a.1.created_at.cmp(&b.1.created_at));
-                    
-                    for (name, task) in sorted_tasks {
-                        let total_time = Task::format_duration(task.get_current_time());
-                        let status = if task.is_running { " (Running)" } else { "" };
-                        
-                        writeln!(file, "Task: {}", name).unwrap();
-                        writeln!(file, "Total Time: {}{}", total_time, status).unwrap();
-                        writeln!(file, "Created: {}", task.created_at.format("%Y-%m-%d %H:%M:%S")).unwrap();
-                        writeln!(file, "").unwrap();
-                    }
-                    
-                    println!("Export successful: {}", path.display());
-                }
-                Err(e) => {
-                    eprintln!("Failed to export: {}", e);
-                }
+                Ok(mut file) => match exporter.export(tasks, &mut file) {
+                    Ok(()) => println!("Export successful: {}", path.display()),
+                    Err(e) => eprintln!("Failed to export: {}", e),
+                },
+                Err(e) => eprintln!("Failed to export: {}", e),
             }
         }
     }
+}
+
+/// Writes tasks to an arbitrary destination in a specific format.
+pub trait Exporter {
+    fn export(&self, tasks: &HashMap<String, Task>, writer: &mut dyn Write) -> Result<(), String>;
+}
+
+/// Returns tasks sorted by creation date, the ordering every exporter uses.
+fn sorted_tasks(tasks: &HashMap<String, Task>) -> Vec<(&String, &Task)> {
+    let mut sorted: Vec<_> = tasks.iter().collect();
+    sorted.sort_by(|a, b| a.1.created_at.cmp(&b.1.created_at));
+    sorted
+}
+
+/// Picks the `Exporter` matching a file extension, defaulting to plain text for anything
+/// unrecognized.
+fn exporter_for_extension(extension: &str) -> Box<dyn Exporter> {
+    match extension.to_lowercase().as_str() {
+        "csv" => Box::new(CsvExporter),
+        "json" => Box::new(JsonExporter),
+        "md" => Box::new(MarkdownTableExporter),
+        _ => Box::new(TxtExporter),
+    }
+}
+
+/// Human-readable plaintext layout, one block per task.
+pub struct TxtExporter;
+
+impl Exporter for TxtExporter {
+    fn export(&self, tasks: &HashMap<String, Task>, writer: &mut dyn Write) -> Result<(), String> {
+        writeln!(writer, "Time Tracker Export").map_err(|e| e.to_string())?;
+        writeln!(writer, "Generated on: {}", chrono::Local::now().format("%Y-%m-%d %H:%M:%S"))
+            .map_err(|e| e.to_string())?;
+        writeln!(writer).map_err(|e| e.to_string())?;
+
+        for (name, task) in sorted_tasks(tasks) {
+            let total_time = Task::format_duration(task.get_current_time());
+            let status = if task.is_running { " (Running)" } else { "" };
+
+            writeln!(writer, "Task: {}", name).map_err(|e| e.to_string())?;
+            writeln!(writer, "Total Time: {}{}", total_time, status).map_err(|e| e.to_string())?;
+            writeln!(writer, "Created: {}", task.created_at.format("%Y-%m-%d %H:%M:%S"))
+                .map_err(|e| e.to_string())?;
+            writeln!(writer).map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+/// Flat `name,total_seconds,created_at,is_running` rows, suitable for spreadsheets.
+pub struct CsvExporter;
+
+impl Exporter for CsvExporter {
+    fn export(&self, tasks: &HashMap<String, Task>, writer: &mut dyn Write) -> Result<(), String> {
+        writeln!(writer, "name,total_seconds,created_at,is_running").map_err(|e| e.to_string())?;
+
+        for (name, task) in sorted_tasks(tasks) {
+            writeln!(
+                writer,
+                "{},{},{},{}",
+                name,
+                task.get_current_time().as_secs(),
+                task.created_at.format("%Y-%m-%d %H:%M:%S"),
+                task.is_running,
+            )
+            .map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+/// Stable JSON array of task objects.
+pub struct JsonExporter;
+
+impl Exporter for JsonExporter {
+    fn export(&self, tasks: &HashMap<String, Task>, writer: &mut dyn Write) -> Result<(), String> {
+        let entries: Vec<serde_json::Value> = sorted_tasks(tasks)
+            .into_iter()
+            .map(|(name, task)| {
+                serde_json::json!({
+                    "name": name,
+                    "total_seconds": task.get_current_time().as_secs(),
+                    "created_at": task.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    "is_running": task.is_running,
+                })
+            })
+            .collect();
+
+        let json_data = serde_json::to_string_pretty(&entries).map_err(|e| e.to_string())?;
+        writer.write_all(json_data.as_bytes()).map_err(|e| e.to_string())
+    }
+}
+
+/// GitHub-flavored Markdown table, one row per task.
+pub struct MarkdownTableExporter;
+
+impl Exporter for MarkdownTableExporter {
+    fn export(&self, tasks: &HashMap<String, Task>, writer: &mut dyn Write) -> Result<(), String> {
+        writeln!(writer, "| Name | Total Time | Created | Running |").map_err(|e| e.to_string())?;
+        writeln!(writer, "| --- | --- | --- | --- |").map_err(|e| e.to_string())?;
+
+        for (name, task) in sorted_tasks(tasks) {
+            writeln!(
+                writer,
+                "| {} | {} | {} | {} |",
+                name,
+                Task::format_duration(task.get_current_time()),
+                task.created_at.format("%Y-%m-%d %H:%M:%S"),
+                task.is_running,
+            )
+            .map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+/// Work/break phase of a Pomodoro cycle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PomodoroPhase {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+/// User-configurable Pomodoro interval lengths.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct PomodoroSettings {
+    pub work_minutes: u32,
+    pub break_minutes: u32,
+    pub long_break_minutes: u32,
+    pub cycles_before_long_break: u32,
+}
+
+impl Default for PomodoroSettings {
+    fn default() -> Self {
+        Self {
+            work_minutes: 25,
+            break_minutes: 5,
+            long_break_minutes: 15,
+            cycles_before_long_break: 4,
+        }
+    }
+}
+
+impl PomodoroSettings {
+    /// Returns the configured length of the given phase, in minutes.
+    pub fn phase_minutes(&self, phase: PomodoroPhase) -> u32 {
+        match phase {
+            PomodoroPhase::Work => self.work_minutes,
+            PomodoroPhase::ShortBreak => self.break_minutes,
+            PomodoroPhase::LongBreak => self.long_break_minutes,
+        }
+    }
+}
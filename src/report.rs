@@ -0,0 +1,65 @@
+use chrono::{Datelike, NaiveDate};
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+use crate::task::Task;
+
+/// A single day's tracked time, used to feed the report bar chart.
+pub struct DailyTotal {
+    pub date: NaiveDate,
+    pub duration: Duration,
+}
+
+/// Aggregates `TimeEntry` durations by `logged_date` across the last `days` days
+/// (oldest first), optionally restricted to a single task.
+pub fn daily_totals(tasks: &HashMap<String, Task>, task_filter: Option<&str>, days: i64) -> Vec<DailyTotal> {
+    let today = chrono::Local::now().date_naive();
+    let mut totals: BTreeMap<NaiveDate, Duration> = BTreeMap::new();
+    for offset in 0..days {
+        totals.insert(today - chrono::Duration::days(offset), Duration::ZERO);
+    }
+
+    for (name, task) in tasks {
+        if let Some(filter) = task_filter {
+            if name != filter {
+                continue;
+            }
+        }
+        for entry in &task.entries {
+            if let Some(total) = totals.get_mut(&entry.logged_date) {
+                *total += entry.duration;
+            }
+        }
+    }
+
+    totals
+        .into_iter()
+        .map(|(date, duration)| DailyTotal { date, duration })
+        .collect()
+}
+
+/// Sums the totals falling within the current ISO week (Monday start).
+pub fn week_total(totals: &[DailyTotal]) -> Duration {
+    let today = chrono::Local::now().date_naive();
+    let week_start = today - chrono::Duration::days(today.weekday().num_days_from_monday() as i64);
+    totals
+        .iter()
+        .filter(|t| t.date >= week_start)
+        .fold(Duration::ZERO, |acc, t| acc + t.duration)
+}
+
+/// Average tracked time per day across the given totals.
+pub fn daily_average(totals: &[DailyTotal]) -> Duration {
+    if totals.is_empty() {
+        return Duration::ZERO;
+    }
+    let total = totals.iter().fold(Duration::ZERO, |acc, t| acc + t.duration);
+    total / totals.len() as u32
+}
+
+/// Name of the task with the most accumulated time, if any tasks exist.
+pub fn most_tracked_task(tasks: &HashMap<String, Task>) -> Option<String> {
+    tasks
+        .iter()
+        .max_by_key(|(_, task)| task.get_current_time())
+        .map(|(name, _)| name.clone())
+}
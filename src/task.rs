@@ -1,17 +1,67 @@
 use serde::{Deserialize, Serialize};
 use std::time::{Duration, Instant};
 use chrono::{DateTime, Local};
+use crate::pomodoro::{PomodoroPhase, PomodoroSettings};
+
+/// Priority level used to highlight and sort tasks. Ordered `Low < Medium < High`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Priority {
+    #[default]
+    Low,
+    Medium,
+    High,
+}
+
+/// Total time logged on a single calendar day, used to drive the day/week report view.
+/// `Task::stop` feeds each completed session through `Task::split_by_day` before
+/// accumulating it here, so a session crossing midnight lands in two entries rather
+/// than being credited entirely to the day it started on.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TimeEntry {
+    pub logged_date: chrono::NaiveDate,
+    #[serde(with = "duration_serde")]
+    pub duration: Duration,
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Task {
     pub name: String,
     #[serde(with = "duration_serde")]
     pub total_time: Duration,
+    /// Per-day log of completed sessions, used for the time-report view.
+    #[serde(default)]
+    pub entries: Vec<TimeEntry>,
     #[serde(skip)] // Don't serialize Instant as it's not meaningful across sessions
     pub start_time: Option<Instant>,
     #[serde(skip)] // Always start as not running when loading
     pub is_running: bool,
     pub created_at: DateTime<Local>,
+    /// Whether Pomodoro mode is currently active for this task.
+    #[serde(default)]
+    pub pomodoro_enabled: bool,
+    /// Number of completed work intervals, persisted across sessions.
+    #[serde(default)]
+    pub completed_pomodoro_cycles: u32,
+    /// Accumulated break time, tracked separately from `total_time`.
+    #[serde(with = "duration_serde", default)]
+    pub break_time: Duration,
+    #[serde(skip)]
+    pub pomodoro_phase: Option<PomodoroPhase>,
+    #[serde(skip)]
+    pub phase_start: Option<Instant>,
+    /// Free-form labels used to group and filter tasks.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Name of the task this one is nested under, if any.
+    #[serde(default)]
+    pub parent: Option<String>,
+    /// Priority used for highlighting and sorting.
+    #[serde(default)]
+    pub priority: Priority,
+    /// When this task was last changed by `start`/`stop`/`reset`, used to resolve
+    /// conflicts when merging state imported from another device.
+    #[serde(default = "Local::now")]
+    pub modified_at: DateTime<Local>,
 }
 
 impl Task {
@@ -19,32 +69,141 @@ impl Task {
         Task {
             name,
             total_time: Duration::new(0, 0),
+            entries: Vec::new(),
             start_time: None,
             is_running: false,
             created_at: Local::now(),
+            pomodoro_enabled: false,
+            completed_pomodoro_cycles: 0,
+            break_time: Duration::new(0, 0),
+            pomodoro_phase: None,
+            phase_start: None,
+            tags: Vec::new(),
+            parent: None,
+            priority: Priority::default(),
+            modified_at: Local::now(),
         }
     }
-    
+
     pub fn start(&mut self) {
         if !self.is_running {
             self.start_time = Some(Instant::now());
             self.is_running = true;
+            self.modified_at = Local::now();
         }
     }
-    
+
     pub fn stop(&mut self) {
         if self.is_running {
             if let Some(start) = self.start_time {
-                self.total_time += start.elapsed();
+                let elapsed = start.elapsed();
+                self.total_time += elapsed;
+
+                let end_dt = Local::now();
+                if let Ok(elapsed_chrono) = chrono::Duration::from_std(elapsed) {
+                    Self::log_session(&mut self.entries, end_dt - elapsed_chrono, end_dt);
+                }
             }
             self.is_running = false;
             self.start_time = None;
+            self.modified_at = Local::now();
         }
     }
-    
+
+    /// Buckets a completed session into `entries`, merging into the existing entry for a
+    /// day if one is already there.
+    fn log_session(entries: &mut Vec<TimeEntry>, start: DateTime<Local>, end: DateTime<Local>) {
+        for (logged_date, duration) in Self::split_by_day(start, end) {
+            match entries.iter_mut().find(|e| e.logged_date == logged_date) {
+                Some(entry) => entry.duration += duration,
+                None => entries.push(TimeEntry { logged_date, duration }),
+            }
+        }
+    }
+
+    /// Breaks a `[start, end)` interval at local midnight boundaries so a session that
+    /// spans multiple calendar days is attributed to each one separately.
+    fn split_by_day(start: DateTime<Local>, end: DateTime<Local>) -> Vec<(chrono::NaiveDate, Duration)> {
+        let mut parts = Vec::new();
+        let mut segment_start = start;
+        while segment_start < end {
+            let next_midnight = (segment_start.date_naive() + chrono::Duration::days(1))
+                .and_hms_opt(0, 0, 0)
+                .and_then(|ndt| {
+                    use chrono::TimeZone;
+                    Local.from_local_datetime(&ndt).single()
+                });
+            let segment_end = match next_midnight {
+                Some(midnight) => end.min(midnight),
+                None => end,
+            };
+
+            if let Ok(duration) = (segment_end - segment_start).to_std() {
+                parts.push((segment_start.date_naive(), duration));
+            }
+
+            segment_start = segment_end;
+        }
+        parts
+    }
+
     pub fn reset(&mut self) {
         self.stop();
         self.total_time = Duration::new(0, 0);
+        self.break_time = Duration::new(0, 0);
+        self.completed_pomodoro_cycles = 0;
+        self.entries.clear();
+        self.modified_at = Local::now();
+    }
+
+    /// Enables Pomodoro mode and begins a new work interval.
+    pub fn start_pomodoro(&mut self) {
+        self.pomodoro_enabled = true;
+        self.pomodoro_phase = Some(PomodoroPhase::Work);
+        self.phase_start = Some(Instant::now());
+        self.start();
+    }
+
+    /// Disables Pomodoro mode and stops tracking.
+    pub fn stop_pomodoro(&mut self) {
+        self.pomodoro_enabled = false;
+        self.pomodoro_phase = None;
+        self.phase_start = None;
+        self.stop();
+    }
+
+    /// Checks whether the current Pomodoro interval has elapsed and, if so,
+    /// transitions to the next phase. Returns the new phase when a transition happened.
+    pub fn tick_pomodoro(&mut self, settings: &PomodoroSettings) -> Option<PomodoroPhase> {
+        let phase = self.pomodoro_phase?;
+        let elapsed = self.phase_start?.elapsed();
+        let phase_len = Duration::from_secs(settings.phase_minutes(phase) as u64 * 60);
+        if elapsed < phase_len {
+            return None;
+        }
+
+        match phase {
+            PomodoroPhase::Work => {
+                // Banks the completed work interval into total_time.
+                self.stop();
+                self.completed_pomodoro_cycles += 1;
+                let next = if self.completed_pomodoro_cycles % settings.cycles_before_long_break.max(1) == 0 {
+                    PomodoroPhase::LongBreak
+                } else {
+                    PomodoroPhase::ShortBreak
+                };
+                self.pomodoro_phase = Some(next);
+                self.phase_start = Some(Instant::now());
+                Some(next)
+            }
+            PomodoroPhase::ShortBreak | PomodoroPhase::LongBreak => {
+                self.break_time += elapsed;
+                self.pomodoro_phase = Some(PomodoroPhase::Work);
+                self.phase_start = Some(Instant::now());
+                self.start();
+                Some(PomodoroPhase::Work)
+            }
+        }
     }
     
     pub fn get_current_time(&self) -> Duration {